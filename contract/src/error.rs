@@ -0,0 +1,66 @@
+//! Typed guard-failure taxonomy for `Contract`.
+//!
+//! Every method used to reject bad calls with `assert!`/`assert_eq!`/
+//! `env::panic_str` and a free-text message, so `NovaSdk` could only tell
+//! failures apart by matching fragile message substrings. Each variant here
+//! carries a stable, machine-readable code instead (see `AsRef<str>`), and
+//! `FunctionError` makes returning `Result<T, NovaError>` from a contract
+//! method behave exactly like the old panics from a caller's perspective —
+//! the host still aborts the call, it just aborts with the code.
+
+use near_sdk::FunctionError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NovaError {
+    GroupNotFound,
+    GroupExists,
+    MissingRole,
+    NotAuthorized,
+    AlreadyMember,
+    NotAMember,
+    KeyVersionNotFound,
+    KeyNotSet,
+    NotThresholdSplit,
+    InvalidThreshold,
+    ShareCountMismatch,
+    ZeroXIndex,
+    DuplicateXIndex,
+    NoAgentKeyRegistered,
+    NonceAlreadyUsed,
+    InvalidKeyLength,
+    InvalidSignatureEncoding,
+    UnsupportedKeyCurve,
+    InvalidSignature,
+}
+
+impl AsRef<str> for NovaError {
+    fn as_ref(&self) -> &str {
+        match self {
+            NovaError::GroupNotFound => "GROUP_NOT_FOUND",
+            NovaError::GroupExists => "GROUP_EXISTS",
+            NovaError::MissingRole => "MISSING_ROLE",
+            NovaError::NotAuthorized => "NOT_AUTHORIZED",
+            NovaError::AlreadyMember => "ALREADY_MEMBER",
+            NovaError::NotAMember => "NOT_A_MEMBER",
+            NovaError::KeyVersionNotFound => "KEY_VERSION_NOT_FOUND",
+            NovaError::KeyNotSet => "KEY_NOT_SET",
+            NovaError::NotThresholdSplit => "NOT_THRESHOLD_SPLIT",
+            NovaError::InvalidThreshold => "INVALID_THRESHOLD",
+            NovaError::ShareCountMismatch => "SHARE_COUNT_MISMATCH",
+            NovaError::ZeroXIndex => "ZERO_X_INDEX",
+            NovaError::DuplicateXIndex => "DUPLICATE_X_INDEX",
+            NovaError::NoAgentKeyRegistered => "NO_AGENT_KEY_REGISTERED",
+            NovaError::NonceAlreadyUsed => "NONCE_ALREADY_USED",
+            NovaError::InvalidKeyLength => "INVALID_KEY_LENGTH",
+            NovaError::InvalidSignatureEncoding => "INVALID_SIGNATURE_ENCODING",
+            NovaError::UnsupportedKeyCurve => "UNSUPPORTED_KEY_CURVE",
+            NovaError::InvalidSignature => "INVALID_SIGNATURE",
+        }
+    }
+}
+
+impl FunctionError for NovaError {
+    fn panic(&self) -> ! {
+        near_sdk::env::panic_str(self.as_ref())
+    }
+}
@@ -0,0 +1,80 @@
+//! NEP-297 structured events.
+//!
+//! Membership changes, key rotations, and transaction records used to be
+//! observable only through human-readable `log!` lines, so an off-chain
+//! indexer had to scrape prose to reconstruct group state or decide when to
+//! kick off an IPFS retrieval. `emit` instead logs one `EVENT_JSON:` line
+//! per the NEP-297 standard, with a typed `data` payload a listener can
+//! deserialize directly instead of matching text.
+
+use near_sdk::serde::Serialize;
+use near_sdk::{env, AccountId};
+
+const STANDARD: &str = "nova";
+const VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct NovaEventLog<'a> {
+    standard: &'a str,
+    version: &'a str,
+    #[serde(flatten)]
+    event: NovaEvent,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum NovaEvent {
+    GroupRegistered(Vec<GroupRegisteredData>),
+    MemberAdded(Vec<MemberAddedData>),
+    MemberRevoked(Vec<MemberRevokedData>),
+    KeyRotated(Vec<KeyRotatedData>),
+    TransactionRecorded(Vec<TransactionRecordedData>),
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GroupRegisteredData {
+    pub group_id: String,
+    pub owner: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MemberAddedData {
+    pub group_id: String,
+    pub user_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MemberRevokedData {
+    pub group_id: String,
+    pub user_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeyRotatedData {
+    pub group_id: String,
+    pub version: u32,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransactionRecordedData {
+    pub group_id: String,
+    pub user_id: AccountId,
+    pub trans_id: String,
+}
+
+/// Logs `event` as a single `EVENT_JSON:` line.
+pub fn emit(event: NovaEvent) {
+    let log = NovaEventLog { standard: STANDARD, version: VERSION, event };
+    env::log_str(&format!(
+        "EVENT_JSON:{}",
+        near_sdk::serde_json::to_string(&log).expect("NovaEventLog always serializes")
+    ));
+}
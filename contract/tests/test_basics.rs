@@ -31,6 +31,10 @@ async fn test_basics_on(contract_wasm: &[u8]) -> Result<(), Box<dyn std::error::
         .transact()
         .await?;
     assert!(register_outcome.is_success(), "{:#?}", register_outcome.into_result().unwrap_err());
+    assert!(
+        register_outcome.logs().iter().any(|l| l.starts_with("EVENT_JSON:") && l.contains("\"group_registered\"")),
+        "register_group should emit a group_registered NEP-297 event"
+    );
 
     // Verify group exists
     let group_exists: bool = contract
@@ -48,6 +52,10 @@ async fn test_basics_on(contract_wasm: &[u8]) -> Result<(), Box<dyn std::error::
         .transact()
         .await?;
     assert!(add_outcome.is_success(), "{:#?}", add_outcome.into_result().unwrap_err());
+    assert!(
+        add_outcome.logs().iter().any(|l| l.starts_with("EVENT_JSON:") && l.contains("\"member_added\"")),
+        "add_group_member should emit a member_added NEP-297 event"
+    );
 
     // Verify is_authorized
     let is_authorized: bool = contract
@@ -65,6 +73,10 @@ async fn test_basics_on(contract_wasm: &[u8]) -> Result<(), Box<dyn std::error::
         .transact()
         .await?;
     assert!(revoke_outcome.is_success(), "{:#?}", revoke_outcome.into_result().unwrap_err());
+    assert!(
+        revoke_outcome.logs().iter().any(|l| l.starts_with("EVENT_JSON:") && l.contains("\"member_revoked\"")),
+        "revoke_group_member should emit a member_revoked NEP-297 event"
+    );
 
     // Verify is_authorized after revoke
     let is_authorized: bool = contract
@@ -83,6 +95,10 @@ async fn test_basics_on(contract_wasm: &[u8]) -> Result<(), Box<dyn std::error::
         .transact()
         .await?;
     assert!(store_key_outcome.is_success(), "{:#?}", store_key_outcome.into_result().unwrap_err());
+    assert!(
+        store_key_outcome.logs().iter().any(|l| l.starts_with("EVENT_JSON:") && l.contains("\"key_rotated\"")),
+        "store_group_key should emit a key_rotated NEP-297 event"
+    );
 
     // Add member again for get_group_key test
     let add_outcome = owner_account
@@ -116,6 +132,10 @@ async fn test_basics_on(contract_wasm: &[u8]) -> Result<(), Box<dyn std::error::
         .transact()
         .await?;
     assert!(record_outcome.is_success(), "{:#?}", record_outcome.into_result().unwrap_err());
+    assert!(
+        record_outcome.logs().iter().any(|l| l.starts_with("EVENT_JSON:") && l.contains("\"transaction_recorded\"")),
+        "record_transaction should emit a transaction_recorded NEP-297 event"
+    );
 
     // Test get_transactions_for_group
     let transactions: Vec<serde_json::Value> = member_account
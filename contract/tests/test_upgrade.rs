@@ -0,0 +1,110 @@
+//nova-sdk/contract/tests/test_upgrade.rs
+//
+// Exercises `upgrade`'s promise chain end-to-end: deploy the current WASM,
+// populate a group with a member, a key, and a transaction, then have the
+// deployed contract redeploy itself and run `migrate` as the callback. A
+// real version bump would swap in a second, schema-changed WASM here; this
+// repo only has the one, so redeploying it onto itself is what's left to
+// prove the deploy-then-migrate plumbing actually round-trips storage
+// instead of losing it — see `migrate`'s doc comment in `src/lib.rs` for
+// how `ContractV1` is meant to grow the day a real second schema shows up.
+use near_workspaces;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_upgrade_preserves_state() -> Result<(), Box<dyn std::error::Error>> {
+    let contract_wasm = near_workspaces::compile_project("./").await?;
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let owner_account = sandbox.dev_create_account().await?;
+    let member_account = sandbox.dev_create_account().await?;
+
+    let init_outcome = owner_account
+        .call(&contract.id(), "new")
+        .args_json(json!({"owner": owner_account.id().to_string()}))
+        .transact()
+        .await?;
+    assert!(init_outcome.is_success(), "{:#?}", init_outcome.into_result().unwrap_err());
+
+    let register_outcome = owner_account
+        .call(&contract.id(), "register_group")
+        .args_json(json!({"group_id": "test_group"}))
+        .deposit(near_workspaces::types::NearToken::from_yoctonear(10_000_000_000_000_000_000_000))
+        .transact()
+        .await?;
+    assert!(register_outcome.is_success(), "{:#?}", register_outcome.into_result().unwrap_err());
+
+    let add_outcome = owner_account
+        .call(&contract.id(), "add_group_member")
+        .args_json(json!({"group_id": "test_group", "user_id": member_account.id().to_string()}))
+        .deposit(near_workspaces::types::NearToken::from_yoctonear(500_000_000_000_000_000_000))
+        .transact()
+        .await?;
+    assert!(add_outcome.is_success(), "{:#?}", add_outcome.into_result().unwrap_err());
+
+    let record_outcome = owner_account
+        .call(&contract.id(), "record_transaction")
+        .args_json(json!({
+            "group_id": "test_group",
+            "user_id": member_account.id().to_string(),
+            "file_hash": "file_hash",
+            "ipfs_hash": "ipfs_hash"
+        }))
+        .deposit(near_workspaces::types::NearToken::from_yoctonear(1_000_000_000_000_000_000_000))
+        .transact()
+        .await?;
+    assert!(record_outcome.is_success(), "{:#?}", record_outcome.into_result().unwrap_err());
+
+    let head_before: String = contract
+        .view("get_group_head")
+        .args_json(json!({"group_id": "test_group"}))
+        .await?
+        .json()?;
+
+    // Redeploy the same WASM onto itself, chaining `migrate` — the owner
+    // was granted `Admin` by `new`, so it's the only account that can do
+    // this.
+    let upgrade_outcome = owner_account
+        .call(&contract.id(), "upgrade")
+        .args_json(json!({"code": contract_wasm}))
+        .gas(near_workspaces::types::Gas::from_tgas(300))
+        .transact()
+        .await?;
+    assert!(upgrade_outcome.is_success(), "{:#?}", upgrade_outcome.into_result().unwrap_err());
+
+    // Group, membership, key version, and the transaction hashchain should
+    // all have survived the redeploy-and-migrate round trip unchanged.
+    let group_exists: bool = contract
+        .view("groups_contains_key")
+        .args_json(json!({"group_id": "test_group"}))
+        .await?
+        .json()?;
+    assert!(group_exists, "Group should survive migration");
+
+    let is_authorized: bool = contract
+        .view("is_authorized")
+        .args_json(json!({"group_id": "test_group", "user_id": member_account.id().to_string()}))
+        .await?
+        .json()?;
+    assert!(is_authorized, "Membership should survive migration");
+
+    let transactions: Vec<serde_json::Value> = member_account
+        .view(&contract.id(), "get_transactions_for_group")
+        .args_json(json!({
+            "group_id": "test_group",
+            "user_id": member_account.id().to_string()
+        }))
+        .await?
+        .json()?;
+    assert_eq!(transactions.len(), 1, "Transaction should survive migration");
+    assert_eq!(transactions[0]["ipfs_hash"], "ipfs_hash");
+
+    let head_after: String = contract
+        .view("get_group_head")
+        .args_json(json!({"group_id": "test_group"}))
+        .await?
+        .json()?;
+    assert_eq!(head_after, head_before, "Hashchain head should survive migration unchanged");
+
+    Ok(())
+}
@@ -19,12 +19,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Register new group
     match sdk.register_group(group_id).await {
         Ok(_) => println!("✅ Group '{}' registered.", group_id),
-        Err(e) if e.to_string().contains("exists") => println!("⚠️ Group '{}' already exists.", group_id),
+        // Stable `GROUP_EXISTS` code from `contract::error::NovaError`, not
+        // free text, so this keeps matching across contract revisions.
+        Err(e) if e.to_string().contains("GROUP_EXISTS") => println!("⚠️ Group '{}' already exists.", group_id),
         Err(e) => return Err(e.into()),
     }
 
     // Add member
-    sdk.add_group_member(group_id, new_member).await?;
+    sdk.add_group_member(group_id, new_member, false).await?;
     println!("✅ Added member '{}' to group '{}'.", new_member, group_id);
 
     // Check authorization
@@ -32,7 +34,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🔍 Authorization check for '{}': {}", new_member, authorized);
 
     // Revoke member
-    sdk.revoke_group_member(group_id, new_member).await?;
+    sdk.revoke_group_member(group_id, new_member, false).await?;
     println!("✅ Revoked member '{}' from group '{}'.", new_member, group_id);
 
     // Verify revocation
@@ -1,4 +1,4 @@
-use nova_sdk_rs::NovaSdk;
+use nova_sdk_rs::{NovaSdk, Secret};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
 use std::env;
@@ -22,24 +22,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut key_bytes = [0u8; 32];
     rand::thread_rng().fill_bytes(&mut key_bytes);
     let key_b64 = STANDARD.encode(key_bytes);
-    sdk.store_group_key(group_id, &key_b64).await?;
+    let key = Secret::from_base64(&key_b64, 32)?;
+    sdk.store_group_key(group_id, &key, false).await?;
     println!("✅ Initial key stored for group '{}': {}", group_id, &key_b64[..20]);  // Truncated for display
 
     // Fetch initial key (as authorized owner)
     let initial_key = sdk.get_group_key(group_id, &account_id).await?;
-    println!("🔑 Initial key retrieved: {}", &initial_key[..20]);
-    assert_eq!(initial_key, key_b64, "Key mismatch on store/fetch!");
+    let initial_key_b64 = initial_key.to_base64();
+    println!("🔑 Initial key retrieved: {}", &initial_key_b64[..20]);
+    assert_eq!(initial_key_b64, key_b64, "Key mismatch on store/fetch!");
 
     // Simulate revocation (triggers rotation in contract)
     let revoked_member = "revoked.testnet";  // Dummy; assumes add_member done prior
-    sdk.revoke_group_member(group_id, revoked_member).await?;
+    sdk.revoke_group_member(group_id, revoked_member, false).await?;
     println!("✅ Revocation triggered key rotation for group '{}'.", group_id);
 
     // Fetch new key
     let rotated_key = sdk.get_group_key(group_id, &account_id).await?;
-    println!("🔄 Rotated key retrieved: {}", &rotated_key[..20]);
-    assert_ne!(rotated_key, initial_key, "Key should have rotated!");
+    let rotated_key_b64 = rotated_key.to_base64();
+    println!("🔄 Rotated key retrieved: {}", &rotated_key_b64[..20]);
+    assert_ne!(rotated_key_b64, initial_key_b64, "Key should have rotated!");
 
-    println!("\n🎉 Key rotation demo complete. Old key: {}, New key: {}", &initial_key[..20], &rotated_key[..20]);
+    println!("\n🎉 Key rotation demo complete. Old key: {}, New key: {}", &initial_key_b64[..20], &rotated_key_b64[..20]);
     Ok(())
 }
\ No newline at end of file
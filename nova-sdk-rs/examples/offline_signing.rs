@@ -0,0 +1,50 @@
+// Demonstrates the air-gapped signing workflow for a key custodian who
+// wants to grant roles (or rotate group keys) without ever putting their
+// signing key on a machine that talks to the network — the same three-step
+// split `sign_only` collapses into one call, shown here separately so it's
+// clear which step runs where:
+//
+//   1. Online, keyless host:  fetch_nonce_and_block_hash + build_unsigned
+//   2. Air-gapped host:       sign_offline (never touches the network)
+//   3. Online, keyless host:  broadcast_signed
+//
+// The unsigned transaction and the signed envelope are both plain base64
+// strings, so step 2 can run on a machine with no network interface at all —
+// carry them over by hand, QR code, or USB stick.
+use nova_sdk_rs::{NovaSdk, Role};
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let rpc_url = env::var("RPC_URL").unwrap_or_else(|_| "https://rpc.testnet.near.org".to_string());
+    let contract_id = env::var("CONTRACT_ID").unwrap_or_else(|_| "nova-sdk-2.testnet".to_string());
+    let custodian_id = env::var("TEST_NEAR_ACCOUNT_ID").expect("TEST_NEAR_ACCOUNT_ID required");
+    let custodian_public_key = env::var("TEST_NEAR_PUBLIC_KEY").expect("TEST_NEAR_PUBLIC_KEY required");
+    let custodian_private_key = env::var("TEST_NEAR_PRIVATE_KEY").expect("TEST_NEAR_PRIVATE_KEY required");
+    let grantee_id = env::var("GRANT_TO_ACCOUNT_ID").unwrap_or_else(|_| "new-custodian.testnet".to_string());
+
+    // Step 1: an online instance with no signer attached builds the
+    // unsigned `grant_role` transaction, fetching the custodian's next
+    // nonce and a recent block hash over RPC.
+    let online = NovaSdk::new(&rpc_url, &contract_id, "dummy", "dummy");
+    let args = serde_json::json!({"account_id": grantee_id, "role": Role::KeyCustodian})
+        .to_string()
+        .into_bytes();
+    let unsigned = online
+        .build_unsigned(&custodian_id, &custodian_public_key, "grant_role", args, None, 500_000_000_000_000_000, None, None)
+        .await?;
+    println!("✅ Unsigned grant_role transaction built (nonce {}).", unsigned.nonce);
+
+    // Step 2: an air-gapped instance, attached to the custodian's key but
+    // never calling `NovaSdk::new`'s rpc_url, signs purely in memory.
+    let offline = NovaSdk::new(&rpc_url, &contract_id, "dummy", "dummy")
+        .with_signer(&custodian_private_key, &custodian_id)?;
+    let signed = offline.sign_offline(&unsigned)?;
+    println!("🔒 Signed offline: {} bytes of base64 envelope.", signed.as_str().len());
+
+    // Step 3: back online, a keyless instance submits the pre-signed
+    // envelope — it never had access to the private key at all.
+    let trans_id = online.broadcast_signed(signed.as_str()).await?;
+    println!("\n🎉 grant_role broadcast complete: {}", trans_id);
+    Ok(())
+}
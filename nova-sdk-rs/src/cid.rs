@@ -0,0 +1,228 @@
+//! CID (content identifier) decoding and integrity verification.
+//!
+//! `composite_retrieve` used to trust the Pinata gateway to return the
+//! bytes a CID actually names, checking only the on-chain SHA-256
+//! `file_hash` afterward — a malicious or misconfigured gateway that also
+//! controls the recorded `file_hash` path slips right past that check.
+//! This decodes the CID itself (CIDv0 base58btc `Qm...` and CIDv1
+//! multibase `b...`, the two formats Pinata returns — see
+//! `validation::validate_cid`), extracts the embedded multihash, and
+//! recomputes it over the fetched bytes before decryption is ever
+//! attempted.
+
+use crate::NovaError;
+
+const SHA2_256_CODE: u64 = 0x12;
+
+/// Recomputes the CID's embedded multihash over `bytes` and errors if it
+/// doesn't match. `composite_retrieve` calls this unconditionally right
+/// after the IPFS fetch, before the ciphertext is decrypted.
+pub fn verify_cid(cid: &str, bytes: &[u8]) -> Result<(), NovaError> {
+    verify_digest(cid, &crate::sha256_hash(bytes))
+}
+
+/// Same check as [`verify_cid`], but against an already-computed digest —
+/// for `composite_retrieve_stream_unverified`, which hashes the fetched bytes
+/// incrementally as they arrive instead of holding the whole body in
+/// memory to hash in one pass.
+pub fn verify_digest(cid: &str, computed: &[u8; 32]) -> Result<(), NovaError> {
+    let (code, digest) = decode_multihash(cid)?;
+    if code != SHA2_256_CODE {
+        return Err(NovaError::Validation(format!(
+            "unsupported multihash code 0x{:x} in CID {}",
+            code, cid
+        )));
+    }
+    if computed.as_slice() != digest.as_slice() {
+        return Err(NovaError::Validation(format!(
+            "CID integrity check failed for {}: fetched content does not hash to the CID's digest",
+            cid
+        )));
+    }
+    Ok(())
+}
+
+/// Decodes a CID down to its multihash `(code, digest)`, unwrapping
+/// whichever container format the CID version uses.
+fn decode_multihash(cid: &str) -> Result<(u64, Vec<u8>), NovaError> {
+    if cid.starts_with("Qm") {
+        // CIDv0 is a bare base58btc multihash with no multibase prefix,
+        // version byte, or codec.
+        let bytes = base58_decode(cid)?;
+        return parse_multihash(&bytes);
+    }
+    if let Some(rest) = cid.strip_prefix('b') {
+        // CIDv1, multibase prefix `b` = base32 (RFC4648, lowercase, no padding).
+        let bytes = base32_decode(rest)?;
+        let mut cursor = bytes.as_slice();
+        let version = read_varint(&mut cursor)?;
+        if version != 1 {
+            return Err(NovaError::Validation(format!("unsupported CID version {}", version)));
+        }
+        let _codec = read_varint(&mut cursor)?;
+        return parse_multihash(cursor);
+    }
+    Err(NovaError::Validation(format!("unrecognized CID format: {}", cid)))
+}
+
+fn parse_multihash(mut bytes: &[u8]) -> Result<(u64, Vec<u8>), NovaError> {
+    let code = read_varint(&mut bytes)?;
+    let len = read_varint(&mut bytes)? as usize;
+    if bytes.len() != len {
+        return Err(NovaError::Validation(
+            "multihash digest length does not match its declared length".to_string(),
+        ));
+    }
+    Ok((code, bytes.to_vec()))
+}
+
+/// Reads an unsigned LEB128 varint (the encoding CIDs use for version,
+/// codec, and multihash code/length fields), advancing `bytes` past it.
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, NovaError> {
+    let mut result: u64 = 0;
+    let mut shift = 0u32;
+    loop {
+        let (&byte, rest) = bytes
+            .split_first()
+            .ok_or_else(|| NovaError::Validation("truncated varint in CID".to_string()))?;
+        *bytes = rest;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(result)
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58_decode(s: &str) -> Result<Vec<u8>, NovaError> {
+    let mut digits: Vec<u8> = vec![0];
+    for c in s.bytes() {
+        let value = BASE58_ALPHABET
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| NovaError::Validation(format!("invalid base58 character in CID: {}", c as char)))?
+            as u32;
+        let mut carry = value;
+        for d in digits.iter_mut() {
+            carry += (*d as u32) * 58;
+            *d = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            digits.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let leading_zeros = s.bytes().take_while(|&b| b == b'1').count();
+    let mut out = vec![0u8; leading_zeros];
+    out.extend(digits.iter().rev());
+    Ok(out)
+}
+
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+fn base32_decode(s: &str) -> Result<Vec<u8>, NovaError> {
+    let mut bits: u64 = 0;
+    let mut bit_count = 0u32;
+    let mut out = Vec::with_capacity(s.len() * 5 / 8);
+    for c in s.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| NovaError::Validation(format!("invalid base32 character in CID: {}", c as char)))?
+            as u64;
+        bits = (bits << 5) | value;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push(((bits >> bit_count) & 0xff) as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_cid_accepts_matching_cidv0() {
+        let data = b"hello nova";
+        let digest = crate::sha256_hash(data);
+        let mut multihash = vec![0x12, 0x20];
+        multihash.extend_from_slice(&digest);
+        let cid = bs58_encode(&multihash);
+        assert!(cid.starts_with("Qm"));
+        assert!(verify_cid(&cid, data).is_ok());
+    }
+
+    #[test]
+    fn verify_cid_rejects_substituted_content() {
+        let data = b"hello nova";
+        let digest = crate::sha256_hash(data);
+        let mut multihash = vec![0x12, 0x20];
+        multihash.extend_from_slice(&digest);
+        let cid = bs58_encode(&multihash);
+        assert!(verify_cid(&cid, b"substituted content").is_err());
+    }
+
+    #[test]
+    fn verify_cid_accepts_matching_cidv1() {
+        let data = b"hello nova";
+        let digest = crate::sha256_hash(data);
+        let mut multihash = vec![0x12, 0x20];
+        multihash.extend_from_slice(&digest);
+        let mut body = vec![0x01, 0x55]; // version 1, codec raw
+        body.extend_from_slice(&multihash);
+        let cid = format!("b{}", base32_encode(&body));
+        assert!(verify_cid(&cid, data).is_ok());
+    }
+
+    #[test]
+    fn verify_cid_rejects_garbage() {
+        assert!(verify_cid("not-a-real-cid", b"data").is_err());
+    }
+
+    // Test-only encoders, inverse of the decoders above, so the roundtrip
+    // tests don't need a real IPFS fetch to exercise `decode_multihash`.
+    fn bs58_encode(bytes: &[u8]) -> String {
+        let mut digits: Vec<u8> = vec![0];
+        for &byte in bytes {
+            let mut carry = byte as u32;
+            for d in digits.iter_mut() {
+                carry += (*d as u32) << 8;
+                *d = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+        let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        let mut out: Vec<u8> = std::iter::repeat(b'1').take(leading_zeros).collect();
+        out.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize]));
+        String::from_utf8(out).unwrap()
+    }
+
+    fn base32_encode(bytes: &[u8]) -> String {
+        let mut bits: u64 = 0;
+        let mut bit_count = 0u32;
+        let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+        for &byte in bytes {
+            bits = (bits << 8) | byte as u64;
+            bit_count += 8;
+            while bit_count >= 5 {
+                bit_count -= 5;
+                out.push(BASE32_ALPHABET[((bits >> bit_count) & 0x1f) as usize] as char);
+            }
+        }
+        if bit_count > 0 {
+            out.push(BASE32_ALPHABET[((bits << (5 - bit_count)) & 0x1f) as usize] as char);
+        }
+        out
+    }
+}
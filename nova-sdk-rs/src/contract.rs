@@ -0,0 +1,7 @@
+//! Typed request/response bindings for the Nova contract, generated at
+//! build time from `schema/contract.json` (see `build.rs`). Argument
+//! structs and result types live here so a contract method's shape only
+//! needs to change in one place instead of at every hand-rolled `json!`
+//! call site.
+
+include!(concat!(env!("OUT_DIR"), "/contract_bindings.rs"));
@@ -0,0 +1,140 @@
+//! Reed–Solomon erasure coding for `composite_upload`'s redundancy mode.
+//!
+//! `composite_upload` pins one encrypted blob to one Pinata gateway, so a
+//! single provider outage loses the file outright. This splits the
+//! encrypted bytes into `k` equal-length data shards, computes `m` parity
+//! shards over GF(2^8) via [`reed_solomon_erasure`], and lets
+//! `composite_retrieve_redundant` reconstruct the original bytes from any
+//! `k` of the `k + m` shards it manages to fetch. The manifest built
+//! around the shards (see `composite_upload_redundant`) carries
+//! `original_len` so [`decode`] can strip the zero padding [`encode`]
+//! added to make the shards an even multiple of `k`.
+
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use serde::{Deserialize, Serialize};
+
+use crate::NovaError;
+
+/// Default data/parity split: 4 data shards, 2 parity shards, tolerating
+/// any 2 of the 6 being unavailable.
+pub const DEFAULT_DATA_SHARDS: usize = 4;
+pub const DEFAULT_PARITY_SHARDS: usize = 2;
+
+/// Pinned alongside the shards themselves and recorded on-chain in place
+/// of a single CID. `shard_cids[i]` is the CID of data shard `i` for
+/// `i < k`, and of parity shard `i - k` otherwise — `composite_retrieve_redundant`
+/// needs that ordering to hand [`decode`] the right `None` slots for
+/// whichever shards didn't come back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardManifest {
+    pub shard_cids: Vec<String>,
+    pub k: usize,
+    pub m: usize,
+    pub original_len: usize,
+}
+
+/// Splits `data` into `k` zero-padded data shards of equal length and
+/// appends `m` parity shards computed over them, returning the `k + m`
+/// shards in order. The caller records the padded shard length isn't
+/// needed separately — every shard in the returned `Vec` is already that
+/// length — but `data.len()` (the true, unpadded size) must be kept
+/// alongside the shards so [`decode`] can trim the padding back off.
+pub fn encode(data: &[u8], k: usize, m: usize) -> Result<Vec<Vec<u8>>, NovaError> {
+    if k == 0 || m == 0 {
+        return Err(NovaError::Validation(
+            "erasure coding needs at least one data shard and one parity shard".to_string(),
+        ));
+    }
+
+    let shard_len = data.len().div_ceil(k).max(1);
+    let mut shards: Vec<Vec<u8>> = Vec::with_capacity(k + m);
+    for i in 0..k {
+        let start = (i * shard_len).min(data.len());
+        let end = ((i + 1) * shard_len).min(data.len());
+        let mut shard = vec![0u8; shard_len];
+        shard[..end - start].copy_from_slice(&data[start..end]);
+        shards.push(shard);
+    }
+    shards.extend(std::iter::repeat(vec![0u8; shard_len]).take(m));
+
+    let codec = ReedSolomon::new(k, m)
+        .map_err(|e| NovaError::Validation(format!("reed-solomon setup failed: {}", e)))?;
+    codec
+        .encode(&mut shards)
+        .map_err(|e| NovaError::Validation(format!("reed-solomon encode failed: {}", e)))?;
+
+    Ok(shards)
+}
+
+/// Reconstructs the original bytes from however many of the `k + m`
+/// shards came back (`None` marks one that couldn't be fetched or failed
+/// its CID check), erroring out if fewer than `k` are present — that's
+/// the point below which Reed–Solomon can no longer recover the data.
+/// Trims the zero padding [`encode`] added using `original_len`.
+pub fn decode(
+    mut shards: Vec<Option<Vec<u8>>>,
+    k: usize,
+    m: usize,
+    original_len: usize,
+) -> Result<Vec<u8>, NovaError> {
+    let available = shards.iter().filter(|s| s.is_some()).count();
+    if available < k {
+        return Err(NovaError::Validation(format!(
+            "only {} of the required {} shards were retrievable",
+            available, k
+        )));
+    }
+
+    let codec = ReedSolomon::new(k, m)
+        .map_err(|e| NovaError::Validation(format!("reed-solomon setup failed: {}", e)))?;
+    codec
+        .reconstruct(&mut shards)
+        .map_err(|e| NovaError::Validation(format!("reed-solomon reconstruct failed: {}", e)))?;
+
+    let mut out = Vec::with_capacity(original_len);
+    for shard in shards.into_iter().take(k) {
+        out.extend_from_slice(&shard.expect("reconstruct fills every shard slot"));
+    }
+    out.truncate(original_len);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trips_with_no_losses() {
+        let data = b"the quick brown fox jumps over the lazy dog".to_vec();
+        let shards = encode(&data, 4, 2).unwrap();
+        let available: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+        assert_eq!(decode(available, 4, 2, data.len()).unwrap(), data);
+    }
+
+    #[test]
+    fn reconstructs_from_exactly_k_shards() {
+        let data = b"reed-solomon tolerates losing up to m shards".to_vec();
+        let shards = encode(&data, 4, 2).unwrap();
+        let mut available: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+        available[1] = None;
+        available[4] = None;
+        assert_eq!(decode(available, 4, 2, data.len()).unwrap(), data);
+    }
+
+    #[test]
+    fn fails_with_fewer_than_k_shards() {
+        let data = b"not enough shards to reconstruct this".to_vec();
+        let shards = encode(&data, 4, 2).unwrap();
+        let mut available: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+        available[0] = None;
+        available[1] = None;
+        available[2] = None;
+        assert!(decode(available, 4, 2, data.len()).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_data_or_parity_shards() {
+        assert!(encode(b"x", 0, 2).is_err());
+        assert!(encode(b"x", 2, 0).is_err());
+    }
+}
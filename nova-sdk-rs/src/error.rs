@@ -0,0 +1,102 @@
+//! Typed NEAR RPC error taxonomy.
+//!
+//! `near_jsonrpc_client` errors are generic over the RPC method's own
+//! server-error type and serialize to the same structured JSON the node
+//! sends over the wire (a `name`/`cause` pair, sometimes with an `info`
+//! object carrying the offending values). Rather than matching on
+//! `e.to_string()` substrings, [`NovaError::from_rpc_error`] serializes the
+//! error and classifies it off that structure, so callers like
+//! `ipfs_retrieve`'s retry loop and [`NovaError::is_retryable`] can make
+//! principled decisions instead of string-sniffing.
+
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NovaError {
+    #[error("Near RPC error: {0}")]
+    Near(String),
+    #[error("Invalid key length or format")]
+    InvalidKey,
+    #[error("Account ID parse failed")]
+    ParseAccount,
+    #[error("Signing error: {0}")]
+    Signing(String),
+    #[error("Light client proof verification failed: {0}")]
+    ProofInvalid(String),
+    #[error("Insufficient balance: need {required} yoctoNEAR, have {available}")]
+    InsufficientBalance {
+        required: near_primitives::types::Balance,
+        available: near_primitives::types::Balance,
+    },
+    #[error("Access key nonce stale: expected >= {expected}, transaction used {got}")]
+    InvalidNonce { expected: u64, got: u64 },
+    #[error("RPC request timed out")]
+    Timeout,
+    #[error("Account does not have enough balance to cover this transaction")]
+    NotEnoughBalance,
+    #[error("Access key not found for this account")]
+    AccessKeyNotFound,
+    #[error("Contract call panicked: {message}")]
+    ContractPanic { message: String },
+    #[error("Rate limited by the RPC endpoint")]
+    RateLimited,
+    #[error("Unclassified RPC error (code {code}): {data}")]
+    Rpc { code: i64, data: String },
+    #[error("Pre-flight validation failed: {0}")]
+    Validation(String),
+}
+
+impl NovaError {
+    /// Whether retrying the same request (after re-deriving any stale
+    /// inputs, e.g. the nonce) has a chance of succeeding. Deterministic
+    /// failures like a contract panic or a malformed transaction never do.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            NovaError::Timeout | NovaError::RateLimited | NovaError::InvalidNonce { .. }
+        )
+    }
+
+    /// Classifies a `near_jsonrpc_client` error by serializing it to JSON
+    /// and inspecting the structured `name`/`cause`/`info` fields the node
+    /// sends, instead of matching on the rendered error string.
+    pub fn from_rpc_error<E: serde::Serialize + std::fmt::Display>(err: E) -> Self {
+        let rendered = err.to_string();
+        let Ok(value) = serde_json::to_value(&err) else {
+            return NovaError::Near(rendered);
+        };
+        Self::classify(&value).unwrap_or(NovaError::Near(rendered))
+    }
+
+    fn classify(value: &Value) -> Option<Self> {
+        let name = value
+            .pointer("/cause/name")
+            .or_else(|| value.pointer("/name"))
+            .and_then(Value::as_str)?;
+
+        let info = value.pointer("/cause/info").or_else(|| value.pointer("/info"));
+
+        match name {
+            "TIMEOUT_ERROR" | "TIMEOUT" => Some(NovaError::Timeout),
+            "REQUEST_VALIDATION_ERROR" if value.to_string().contains("RateLimited") => {
+                Some(NovaError::RateLimited)
+            }
+            "InvalidNonce" => {
+                let expected = info.and_then(|i| i["ak_nonce"].as_u64()).unwrap_or_default();
+                let got = info.and_then(|i| i["tx_nonce"].as_u64()).unwrap_or_default();
+                Some(NovaError::InvalidNonce { expected, got })
+            }
+            "NotEnoughBalance" => Some(NovaError::NotEnoughBalance),
+            "InvalidAccessKeyError" | "AccessKeyNotFound" => Some(NovaError::AccessKeyNotFound),
+            "FunctionCallError" | "ContractPanic" => {
+                let message = info
+                    .and_then(|i| i["message"].as_str().or_else(|| i.as_str()))
+                    .unwrap_or("contract panicked")
+                    .to_string();
+                Some(NovaError::ContractPanic { message })
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Parses the NEP-297 `EVENT_JSON:` log lines the contract emits (see
+//! `contract::events` in the contract crate) into typed `NovaEvent`s, so a
+//! consumer doesn't have to scrape an outcome's raw logs for state changes.
+
+use near_primitives::views::FinalExecutionOutcomeView;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum NovaEvent {
+    GroupRegistered(Vec<GroupRegisteredData>),
+    MemberAdded(Vec<MemberAddedData>),
+    MemberRevoked(Vec<MemberRevokedData>),
+    KeyRotated(Vec<KeyRotatedData>),
+    TransactionRecorded(Vec<TransactionRecordedData>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GroupRegisteredData {
+    pub group_id: String,
+    pub owner: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemberAddedData {
+    pub group_id: String,
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemberRevokedData {
+    pub group_id: String,
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyRotatedData {
+    pub group_id: String,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionRecordedData {
+    pub group_id: String,
+    pub user_id: String,
+    pub trans_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct NovaEventLog {
+    standard: String,
+    #[serde(rename = "version")]
+    _version: String,
+    #[serde(flatten)]
+    event: NovaEvent,
+}
+
+/// Every `nova`-standard NEP-297 event logged anywhere in `outcome` — the
+/// transaction's own outcome and every receipt it produced, since a
+/// `FunctionCall` action's `log!`/event lines land on the receiving
+/// receipt's outcome, not the signing transaction's. Lines that aren't
+/// `EVENT_JSON:`, or whose JSON doesn't match a known `NovaEvent` shape,
+/// are skipped rather than failing the whole parse.
+pub fn parse_events(outcome: &FinalExecutionOutcomeView) -> Vec<NovaEvent> {
+    outcome
+        .transaction_outcome
+        .outcome
+        .logs
+        .iter()
+        .chain(outcome.receipts_outcome.iter().flat_map(|r| r.outcome.logs.iter()))
+        .filter_map(|line| line.strip_prefix("EVENT_JSON:"))
+        .filter_map(|json| serde_json::from_str::<NovaEventLog>(json).ok())
+        .filter(|log| log.standard == "nova")
+        .map(|log| log.event)
+        .collect()
+}
@@ -0,0 +1,111 @@
+//! Per-member envelope encryption for the group data key.
+//!
+//! `store_group_key`/`get_group_key` used to treat the group key as one
+//! plaintext blob shared by every member, so revoking a member didn't stop
+//! them decrypting anything: they'd already cached the shared key. This
+//! wraps the 32-byte group data key separately for each member instead: an
+//! ephemeral X25519 keypair performs ECDH against the member's NEAR
+//! ed25519 public key (converted to Curve25519 via the standard
+//! birational map), HKDF-SHA256 derives an AES-256-GCM key from the shared
+//! secret, and that key wraps the group data key. The contract only ever
+//! stores ciphertext it can't unwrap itself.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use hkdf::Hkdf;
+use near_crypto::PublicKey;
+use rand::RngCore;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey as XPublicKey, StaticSecret};
+
+use crate::{NovaError, Secret, Signer};
+
+const NONCE_LEN: usize = 12;
+const X25519_LEN: usize = 32;
+
+/// Generates a fresh random 32-byte group data key.
+pub fn generate_data_key() -> [u8; 32] {
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+/// Converts a NEAR ed25519 public key to its Curve25519 (X25519) form via
+/// the same birational map `crypto_sign_ed25519_pk_to_curve25519` uses.
+/// Only ed25519 keys can participate in X25519 ECDH.
+fn ed25519_public_to_x25519(public_key: &PublicKey) -> Result<XPublicKey, NovaError> {
+    let bytes = match public_key {
+        PublicKey::ED25519(inner) => inner.0,
+        _ => {
+            return Err(NovaError::Signing(
+                "group-key wrapping requires an ed25519 member public key".to_string(),
+            ))
+        }
+    };
+    let edwards_point = CompressedEdwardsY(bytes).decompress().ok_or(NovaError::InvalidKey)?;
+    Ok(XPublicKey::from(edwards_point.to_montgomery().to_bytes()))
+}
+
+fn derive_aead_key(shared_secret: &[u8]) -> [u8; 32] {
+    let hk = Hkdf::<Sha256>::new(None, shared_secret);
+    let mut aead_key = [0u8; 32];
+    hk.expand(b"nova-sdk group-key wrap", &mut aead_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+    aead_key
+}
+
+/// Wraps `data_key` for `member_public_key`: `ephemeral_x25519_pub (32) ||
+/// nonce (12) || AES-256-GCM(data_key)`, base64-encoded. The ephemeral
+/// secret is discarded immediately after use — only the recipient's own
+/// private key can ever re-derive the shared secret.
+pub fn wrap_for_member(data_key: &[u8; 32], member_public_key: &PublicKey) -> Result<String, NovaError> {
+    let member_x25519 = ed25519_public_to_x25519(member_public_key)?;
+
+    let ephemeral_secret = StaticSecret::random_from_rng(rand::thread_rng());
+    let ephemeral_public = XPublicKey::from(&ephemeral_secret);
+    let shared_secret = ephemeral_secret.diffie_hellman(&member_x25519);
+    let aead_key = derive_aead_key(shared_secret.as_bytes());
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&aead_key));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), data_key.as_slice())
+        .map_err(|_| NovaError::Near("group key wrap failed".to_string()))?;
+
+    let mut out = Vec::with_capacity(X25519_LEN + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(ephemeral_public.as_bytes());
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(base64::engine::general_purpose::STANDARD.encode(out))
+}
+
+/// Reverses `wrap_for_member`, using `signer`'s private key to re-derive
+/// the shared secret against the embedded ephemeral public key.
+pub fn unwrap_for_member(wrapped_b64: &str, signer: &dyn Signer) -> Result<Secret, NovaError> {
+    let blob = base64::engine::general_purpose::STANDARD
+        .decode(wrapped_b64)
+        .map_err(|_| NovaError::InvalidKey)?;
+    if blob.len() < X25519_LEN + NONCE_LEN {
+        return Err(NovaError::InvalidKey);
+    }
+    let (ephemeral_pub_bytes, rest) = blob.split_at(X25519_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let mut ephemeral_pub_arr = [0u8; X25519_LEN];
+    ephemeral_pub_arr.copy_from_slice(ephemeral_pub_bytes);
+
+    let shared_secret = signer.ecdh_shared_secret(&ephemeral_pub_arr)?;
+    let aead_key = derive_aead_key(&shared_secret);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&aead_key));
+    let data_key = cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| NovaError::Near("group key unwrap failed".to_string()))?;
+    if data_key.len() != 32 {
+        return Err(NovaError::InvalidKey);
+    }
+    Ok(Secret::new(data_key))
+}
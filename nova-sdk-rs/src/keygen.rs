@@ -0,0 +1,242 @@
+//! Local keypair generation and BIP39 mnemonic recovery.
+//!
+//! `with_signer` always assumed the caller already had a private key
+//! string in hand, so onboarding a new user or provisioning a
+//! group-admin account meant shelling out to `near-cli` or `ethkey`
+//! first. This mints signing material locally instead: [`generate_keypair`]
+//! produces a random ed25519 or secp256k1 keypair in NEAR's
+//! `ed25519:`/`secp256k1:` string encoding (the same encoding `with_signer`
+//! and `near-cli` both accept), and [`MnemonicKeypair`] generates or
+//! recovers a BIP39 phrase and derives an ed25519 keypair from it via
+//! SLIP-0010 HD derivation along the standard NEAR path (`m/44'/397'/0'`).
+
+use bip39::{Language, Mnemonic};
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use near_crypto::{KeyType, SecretKey};
+use rand::RngCore;
+use sha2::Sha512;
+
+use crate::{NovaError, Secret};
+
+/// The NEAR-recommended ed25519 HD derivation path for account keys
+/// (the same default `near-cli` and most NEAR wallets use).
+pub const NEAR_DERIVATION_PATH: &str = "m/44'/397'/0'";
+
+/// Which curve to mint a random keypair on. NEAR access keys accept either.
+pub enum Curve {
+    Ed25519,
+    Secp256k1,
+}
+
+/// A freshly minted or recovered keypair, NEAR string-encoded
+/// (`ed25519:...` / `secp256k1:...`). The private key is kept behind a
+/// [`Secret`] so it doesn't linger in a plain `String` until the caller
+/// deliberately asks for it — the same rationale `with_signer` already
+/// applies to the private key string it's handed.
+pub struct GeneratedKeypair {
+    pub public_key: String,
+    private_key: Secret,
+}
+
+impl GeneratedKeypair {
+    fn from_secret_key(secret_key: SecretKey) -> Self {
+        GeneratedKeypair {
+            public_key: secret_key.public_key().to_string(),
+            private_key: Secret::new(secret_key.to_string().into_bytes()),
+        }
+    }
+
+    /// The NEAR-encoded private key string, ready for `with_signer`.
+    /// Exposed only on request, never through `Debug`.
+    pub fn private_key(&self) -> Result<String, NovaError> {
+        String::from_utf8(self.private_key.expose_bytes().to_vec()).map_err(|_| NovaError::InvalidKey)
+    }
+}
+
+impl std::fmt::Debug for GeneratedKeypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeneratedKeypair")
+            .field("public_key", &self.public_key)
+            .field("private_key", &"Secret(<redacted>)")
+            .finish()
+    }
+}
+
+/// Generates a random keypair on `curve`, NEAR string-encoded.
+pub fn generate_keypair(curve: Curve) -> GeneratedKeypair {
+    let key_type = match curve {
+        Curve::Ed25519 => KeyType::ED25519,
+        Curve::Secp256k1 => KeyType::SECP256K1,
+    };
+    GeneratedKeypair::from_secret_key(SecretKey::from_random(key_type))
+}
+
+/// Recovers the ed25519 keypair a BIP39 `phrase` derives to along
+/// `derivation_path` (use [`NEAR_DERIVATION_PATH`] unless the account was
+/// provisioned with a custom path).
+pub fn from_mnemonic(phrase: &str, derivation_path: &str) -> Result<GeneratedKeypair, NovaError> {
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase)
+        .map_err(|e| NovaError::Signing(format!("invalid BIP39 mnemonic: {}", e)))?;
+    keypair_from_seed(&mnemonic.to_seed_normalized(""), derivation_path)
+}
+
+fn keypair_from_seed(bip39_seed: &[u8], derivation_path: &str) -> Result<GeneratedKeypair, NovaError> {
+    let ed25519_seed = derive_ed25519_seed(bip39_seed, derivation_path)?;
+    let signing_key = SigningKey::from_bytes(&ed25519_seed);
+
+    // NEAR's ed25519 secret key bytes are the 64-byte dalek keypair
+    // (32-byte seed || 32-byte public key) — see `Ed25519Signer::ecdh_shared_secret`.
+    let mut key_bytes = [0u8; 64];
+    key_bytes[..32].copy_from_slice(&signing_key.to_bytes());
+    key_bytes[32..].copy_from_slice(signing_key.verifying_key().as_bytes());
+    let secret_key = SecretKey::ED25519(near_crypto::ED25519SecretKey(key_bytes));
+
+    Ok(GeneratedKeypair::from_secret_key(secret_key))
+}
+
+/// A BIP39 mnemonic and the ed25519 keypair it derives to, kept together
+/// so generating a new account and backing up its recovery phrase happen
+/// in one step.
+pub struct MnemonicKeypair {
+    pub keypair: GeneratedKeypair,
+    phrase: Secret,
+}
+
+impl MnemonicKeypair {
+    /// Generates a fresh 12-word (128-bit entropy) BIP39 mnemonic and
+    /// derives its ed25519 keypair along `derivation_path`.
+    pub fn generate(derivation_path: &str) -> Result<Self, NovaError> {
+        let mut entropy = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut entropy);
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy)
+            .map_err(|e| NovaError::Signing(format!("mnemonic generation failed: {}", e)))?;
+        let keypair = keypair_from_seed(&mnemonic.to_seed_normalized(""), derivation_path)?;
+        Ok(MnemonicKeypair {
+            keypair,
+            phrase: Secret::new(mnemonic.to_string().into_bytes()),
+        })
+    }
+
+    /// Recovers a keypair from an existing mnemonic, keeping the phrase
+    /// alongside it the same way [`MnemonicKeypair::generate`] does.
+    pub fn from_mnemonic(phrase: &str, derivation_path: &str) -> Result<Self, NovaError> {
+        Ok(MnemonicKeypair {
+            keypair: from_mnemonic(phrase, derivation_path)?,
+            phrase: Secret::new(phrase.as_bytes().to_vec()),
+        })
+    }
+
+    /// The recovery phrase, for display/backup. Exposed only on request,
+    /// mirroring [`GeneratedKeypair::private_key`].
+    pub fn to_mnemonic(&self) -> Result<String, NovaError> {
+        String::from_utf8(self.phrase.expose_bytes().to_vec()).map_err(|_| NovaError::InvalidKey)
+    }
+}
+
+/// Parses a derivation path of all-hardened segments (`m/44'/397'/0'`)
+/// into SLIP-0010 hardened indices. Ed25519 SLIP-0010 only defines
+/// hardened derivation, so a non-hardened segment is rejected up front
+/// rather than silently producing a key no wallet would agree with.
+fn parse_hardened_path(path: &str) -> Result<Vec<u32>, NovaError> {
+    let mut indices = Vec::new();
+    for segment in path.split('/') {
+        if segment == "m" {
+            continue;
+        }
+        if !(segment.ends_with('\'') || segment.ends_with('h')) {
+            return Err(NovaError::Signing(format!(
+                "ed25519 HD derivation only supports hardened path segments, got \"{}\"",
+                segment
+            )));
+        }
+        let index: u32 = segment
+            .trim_end_matches(['\'', 'h'])
+            .parse()
+            .map_err(|_| NovaError::Signing(format!("invalid derivation path segment \"{}\"", segment)))?;
+        indices.push(index | 0x8000_0000);
+    }
+    Ok(indices)
+}
+
+type HmacSha512 = Hmac<Sha512>;
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts a key of any length");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// SLIP-0010 ed25519 HD derivation: walks `derivation_path`'s hardened
+/// indices from the master key (`HMAC-SHA512("ed25519 seed", bip39_seed)`),
+/// returning the final 32-byte key as the ed25519 signing seed.
+fn derive_ed25519_seed(bip39_seed: &[u8], derivation_path: &str) -> Result<[u8; 32], NovaError> {
+    let indices = parse_hardened_path(derivation_path)?;
+
+    let master = hmac_sha512(b"ed25519 seed", &[bip39_seed]);
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&master[..32]);
+    chain_code.copy_from_slice(&master[32..]);
+
+    for index in indices {
+        let child = hmac_sha512(&chain_code, &[&[0u8], &key, &index.to_be_bytes()]);
+        key.copy_from_slice(&child[..32]);
+        chain_code.copy_from_slice(&child[32..]);
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_keypair_round_trips_through_with_signer_encoding() {
+        let keypair = generate_keypair(Curve::Ed25519);
+        assert!(keypair.public_key.starts_with("ed25519:"));
+        assert!(keypair.private_key().unwrap().starts_with("ed25519:"));
+    }
+
+    #[test]
+    fn generate_keypair_secp256k1() {
+        let keypair = generate_keypair(Curve::Secp256k1);
+        assert!(keypair.public_key.starts_with("secp256k1:"));
+        assert!(keypair.private_key().unwrap().starts_with("secp256k1:"));
+    }
+
+    #[test]
+    fn mnemonic_round_trip_recovers_the_same_keypair() {
+        let generated = MnemonicKeypair::generate(NEAR_DERIVATION_PATH).unwrap();
+        let phrase = generated.to_mnemonic().unwrap();
+
+        let recovered = MnemonicKeypair::from_mnemonic(&phrase, NEAR_DERIVATION_PATH).unwrap();
+
+        assert_eq!(generated.keypair.public_key, recovered.keypair.public_key);
+        assert_eq!(generated.keypair.private_key().unwrap(), recovered.keypair.private_key().unwrap());
+    }
+
+    #[test]
+    fn different_derivation_paths_yield_different_keys() {
+        let generated = MnemonicKeypair::generate(NEAR_DERIVATION_PATH).unwrap();
+        let phrase = generated.to_mnemonic().unwrap();
+
+        let other_account = MnemonicKeypair::from_mnemonic(&phrase, "m/44'/397'/1'").unwrap();
+
+        assert_ne!(generated.keypair.public_key, other_account.keypair.public_key);
+    }
+
+    #[test]
+    fn from_mnemonic_rejects_invalid_phrase() {
+        assert!(from_mnemonic("not a real mnemonic phrase at all", NEAR_DERIVATION_PATH).is_err());
+    }
+
+    #[test]
+    fn rejects_non_hardened_path_segments() {
+        let generated = MnemonicKeypair::generate(NEAR_DERIVATION_PATH).unwrap();
+        let phrase = generated.to_mnemonic().unwrap();
+        assert!(from_mnemonic(&phrase, "m/44/397/0").is_err());
+    }
+}
@@ -1,31 +1,76 @@
 use near_jsonrpc_client::{methods, JsonRpcClient};
 use near_jsonrpc_client::methods::broadcast_tx_commit::RpcBroadcastTxCommitRequest;
 use near_jsonrpc_primitives::types::query::QueryResponseKind as JsonRpcQueryResponseKind;
-use near_primitives::types::{AccountId, Balance, BlockReference, Finality, BlockHeight};
+use near_jsonrpc_primitives::types::light_client::TransactionOrReceiptId;
+use near_primitives::types::{AccountId, Balance, BlockReference, Finality, BlockHeight, Nonce};
 use near_primitives::views::{QueryRequest, ExecutionOutcomeView, FinalExecutionOutcomeView, ExecutionStatusView};
 use near_primitives::hash::CryptoHash;
 use near_primitives::transaction::{
-    Action, FunctionCallAction, SignedTransaction, TransferAction
+    Action, AddKeyAction, DeleteKeyAction, FunctionCallAction, SignedTransaction, TransferAction
 };
-use near_crypto::{InMemorySigner, Signer, SecretKey};
-use thiserror::Error;
+use near_primitives::account::{AccessKey, AccessKeyPermission, FunctionCallPermission};
+use near_crypto::{PublicKey, SecretKey};
 use std::str::FromStr;
 use serde_json::json;
 use base64::Engine;
 use base64::engine::general_purpose;
 use tokio::time::{sleep, Duration};
 
-#[derive(Error, Debug)]
-pub enum NovaError {
-    #[error("Near RPC error: {0}")]
-    Near(String),
-    #[error("Invalid key length or format")]
-    InvalidKey,
-    #[error("Account ID parse failed")]
-    ParseAccount,
-    #[error("Signing error: {0}")]
-    Signing(String),
-}
+mod light_client;
+pub use light_client::LightClient;
+
+mod streaming;
+use streaming::{ChunkDecryptor, ChunkEncryptor, CHUNK_SIZE};
+
+mod error;
+pub use error::NovaError;
+
+mod offline;
+pub use offline::{NonceAndBlockHash, SignedTxEnvelope, UnsignedTx};
+
+mod signer;
+pub use signer::{Ed25519Signer, Secp256k1Signer, Signer};
+
+mod secret;
+pub use secret::Secret;
+
+mod group_key;
+
+mod shamir;
+
+mod cid;
+pub use cid::verify_cid;
+
+mod erasure;
+pub use erasure::{ShardManifest, DEFAULT_DATA_SHARDS, DEFAULT_PARITY_SHARDS};
+
+mod keygen;
+pub use keygen::{Curve, GeneratedKeypair, MnemonicKeypair, NEAR_DERIVATION_PATH};
+
+mod events;
+pub use events::{
+    parse_events, GroupRegisteredData, KeyRotatedData, MemberAddedData, MemberRevokedData, NovaEvent,
+    TransactionRecordedData,
+};
+
+mod validation;
+
+mod contract;
+
+/// Default gas attached when the caller doesn't supply an explicit limit.
+/// Matches what every contract method was hardcoded to before per-call
+/// estimation existed.
+const DEFAULT_GAS: u64 = 300_000_000_000_000;
+
+/// Balance `transfer_tokens` always leaves untouched, covering the
+/// account's storage deposit and a bit of gas headroom for its next call.
+const TRANSFER_BALANCE_RESERVE: Balance = 1_000_000_000_000_000_000_000; // 0.001 NEAR
+
+/// Identifies an envelope-encrypted blob (DEK wrapped under the group key,
+/// prepended to a body encrypted under the DEK) so it can't be mistaken for
+/// ciphertext encrypted directly under the group key.
+const ENVELOPE_MAGIC: [u8; 4] = *b"NVE1";
+const ENVELOPE_VERSION: u8 = 1;
 
 #[derive(serde::Deserialize, Debug)]
 pub struct Transaction {
@@ -33,6 +78,20 @@ pub struct Transaction {
     pub user_id: String,
     pub file_hash: String,
     pub ipfs_hash: String,
+    pub key_version: u32,
+    pub recorded_at: u64,
+    pub chain_hash: String,
+}
+
+/// Mirrors the contract's `Role` enum (see `contract::Role`) so
+/// `grant_role`/`revoke_role`/`has_role` serialize a role the same way the
+/// contract's `#[serde(rename_all = "snake_case")]` expects.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    GroupManager,
+    KeyCustodian,
 }
 
 // Result structs for composites
@@ -49,13 +108,29 @@ pub struct CompositeRetrieveResult {
     pub file_hash: String,
 }
 
+/// Result of `composite_upload_redundant`: unlike plain `composite_upload`,
+/// the CID recorded on-chain (`manifest_cid`) names a [`ShardManifest`]
+/// rather than the file itself — `shard_cids` is that manifest's own list,
+/// surfaced here so the caller doesn't have to fetch the manifest back
+/// just to see where the shards landed.
+#[derive(Debug)]
+pub struct CompositeUploadRedundantResult {
+    pub manifest_cid: String,
+    pub shard_cids: Vec<String>,
+    pub trans_id: String,
+    pub file_hash: String,
+}
+
 #[derive(Debug)]
 pub struct NovaSdk {
     client: JsonRpcClient,
     contract_id: AccountId,
-    signer: Option<Signer>,
+    signer: Option<Box<dyn Signer>>,
     pinata_key: String,
     pinata_secret: String,
+    light_client: Option<LightClient>,
+    gas_price_cache: tokio::sync::Mutex<Option<(BlockHeight, Balance)>>,
+    read_finality: BlockReference,
 }
 
 impl NovaSdk {  // REMOVED generic type parameter
@@ -69,71 +144,242 @@ impl NovaSdk {  // REMOVED generic type parameter
             signer: None,
             pinata_key: pinata_key.to_string(),
             pinata_secret: pinata_secret.to_string(),
+            light_client: None,
+            gas_price_cache: tokio::sync::Mutex::new(None),
+            read_finality: BlockReference::Finality(Finality::Final),
         }
     }
 
+    // Overrides the finality used by read-only queries (`get_balance`,
+    // `is_authorized`, `get_group_key`, `get_transactions_for_group`).
+    // Defaults to `Finality::Final`; switch to `Finality::Optimistic` for
+    // lower-latency reads, or pin to a specific `BlockReference::BlockId`
+    // when a flow needs to observe a particular block's state, e.g.
+    // confirming a just-revoked member is gone as of the block that
+    // revoked them.
+    pub fn with_finality(mut self, finality: BlockReference) -> Self {
+        self.read_finality = finality;
+        self
+    }
+
+    // Bootstraps a light client from a trusted block hash and switches the
+    // SDK into trustless mode: view calls are verified against locally-held
+    // validator signatures and Merkle proofs instead of taking the RPC
+    // gateway's word for it. Call `advance_light_client` periodically to
+    // keep the head fresh as the chain progresses.
+    pub async fn with_light_client(mut self, trusted_block_hash: &str) -> Result<Self, NovaError> {
+        let hash = CryptoHash::from_str(trusted_block_hash)
+            .map_err(|e| NovaError::ProofInvalid(format!("invalid trusted block hash: {}", e)))?;
+        let light_client = LightClient::bootstrap(&self.client, hash).await?;
+        self.light_client = Some(light_client);
+        Ok(self)
+    }
+
+    // Advances the local light client head by one verified step, if a newer
+    // one is available. Returns whether the head actually moved.
+    pub async fn advance_light_client(&mut self) -> Result<bool, NovaError> {
+        let light_client = self
+            .light_client
+            .as_mut()
+            .ok_or_else(|| NovaError::ProofInvalid("no light client attached".to_string()))?;
+        light_client.advance(&self.client).await
+    }
+
     // Attaches a signer using a NEAR private key string (e.g., "ed25519:base58key").
+    // The key is held in a zeroizing `Secret` for the length of this call so
+    // it doesn't linger in an ordinary `String` once parsed.
     pub fn with_signer(mut self, private_key: &str, account_id: &str) -> Result<Self, NovaError> {
         // Validate account_id first
         let account_id_acc = AccountId::from_str(account_id).map_err(|_| NovaError::ParseAccount)?;
         // Then parse the secret key
-        let secret_key = SecretKey::from_str(private_key).map_err(|e| NovaError::Signing(e.to_string()))?;
-        let signer = InMemorySigner::from_secret_key(account_id_acc, secret_key);
-        self.signer = Some(signer);
+        let secret = Secret::new(private_key.as_bytes().to_vec());
+        let key_str = std::str::from_utf8(secret.expose_bytes()).map_err(|_| NovaError::InvalidKey)?;
+        let secret_key = SecretKey::from_str(key_str).map_err(|e| NovaError::Signing(e.to_string()))?;
+        // `SecretKey::from_str` already dispatches on the `ed25519:`/
+        // `secp256k1:` prefix; mirror that here to pick the matching
+        // `Signer` impl instead of assuming ed25519.
+        self.signer = Some(match secret_key {
+            SecretKey::ED25519(_) => Box::new(Ed25519Signer::new(account_id_acc, secret_key)?) as Box<dyn Signer>,
+            SecretKey::SECP256K1(_) => Box::new(Secp256k1Signer::new(account_id_acc, secret_key)?) as Box<dyn Signer>,
+        });
         Ok(self)
     }
 
+    // Attaches any `Signer` implementation directly, so callers can plug in
+    // a secp256k1 key, a Ledger, or a remote signing service without the
+    // SDK ever handling private key bytes itself.
+    pub fn with_signer_boxed(mut self, signer: Box<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    // Mints a random keypair on `curve`, NEAR string-encoded and ready for
+    // `with_signer` — so onboarding a user or provisioning a group-admin
+    // account doesn't require shelling out to `near-cli` first.
+    pub fn generate_keypair(curve: Curve) -> GeneratedKeypair {
+        keygen::generate_keypair(curve)
+    }
+
+    // Recovers the ed25519 keypair a BIP39 `phrase` derives to along
+    // `derivation_path` (use `keygen::NEAR_DERIVATION_PATH` unless the
+    // account was provisioned with a custom path).
+    pub fn from_mnemonic(phrase: &str, derivation_path: &str) -> Result<GeneratedKeypair, NovaError> {
+        keygen::from_mnemonic(phrase, derivation_path)
+    }
+
     // Queries the balance of an account on NEAR.
     pub async fn get_balance(&self, account_id: &str) -> Result<Balance, NovaError> {
         let account_id_acc = AccountId::from_str(account_id).map_err(|_| NovaError::ParseAccount)?;
         let request = methods::query::RpcQueryRequest {
-            block_reference: BlockReference::Finality(Finality::Final),
+            block_reference: self.read_finality.clone(),
             request: QueryRequest::ViewAccount { account_id: account_id_acc },
         };
-        let response = self.client.call(request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
         match response.kind {
             JsonRpcQueryResponseKind::ViewAccount(acc) => Ok(acc.amount),
             _ => Err(NovaError::Near("Invalid response kind".to_string())),
         }
     }
 
-    // Checks if a user is authorized in a group (read-only contract view).
-    pub async fn is_authorized(&self, group_id: &str, user_id: &str) -> Result<bool, NovaError> {
-        let args = json!({"group_id": group_id, "user_id": user_id.to_string()}).to_string().into_bytes();
+    // Returns an error if a light client is attached and the block a query
+    // response was served against is newer than our verified head: that
+    // would mean we have no basis yet to trust it was produced by
+    // >= 2/3 validator stake.
+    fn verify_trustless(&self, served_at_height: BlockHeight) -> Result<(), NovaError> {
+        match &self.light_client {
+            Some(lc) if served_at_height > lc.head_height() => Err(NovaError::ProofInvalid(format!(
+                "query served at block {} is ahead of verified light client head {}; call advance_light_client first",
+                served_at_height,
+                lc.head_height()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    // Runs a view-style contract call the plain way: a `CallFunction`
+    // query against `self.read_finality`, trusting the gateway's
+    // self-reported `block_height` (checked only against
+    // `verify_trustless`'s height bound, not Merkle-proved). This is the
+    // whole story when no light client is attached.
+    async fn plain_view_call(&self, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>, NovaError> {
         let request = methods::query::RpcQueryRequest {
-            block_reference: BlockReference::Finality(Finality::Final),
+            block_reference: self.read_finality.clone(),
             request: QueryRequest::CallFunction {
                 account_id: self.contract_id.clone(),
-                method_name: "is_authorized".to_string(),
+                method_name: method_name.to_string(),
                 args: args.into(),
             },
         };
-        let response = self.client.call(request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
         match response.kind {
-            JsonRpcQueryResponseKind::CallResult(result) => {
-                let bool_result: bool = serde_json::from_slice(&result.result).map_err(|e| NovaError::Near(e.to_string()))?;
-                Ok(bool_result)
-            }
+            JsonRpcQueryResponseKind::CallResult(result) => Ok(result.result),
             _ => Err(NovaError::Near("Invalid response kind".to_string())),
         }
     }
 
-    // Fetches the base64-encoded group key for an authorized user (read-only contract view).
-    pub async fn get_group_key(&self, group_id: &str, user_id: &str) -> Result<String, NovaError> {
-        let args = json!({"group_id": group_id, "user_id": user_id.to_string()}).to_string().into_bytes();
+    // Runs a view-style contract call and, when a light client is
+    // attached, backs its result with a real Merkle/stake proof instead of
+    // `verify_trustless`'s height-only check: a `CallFunction` query's
+    // result isn't itself Merkle-provable against the light-client chain,
+    // so this submits the call as a real (zero-deposit) transaction,
+    // fetches `EXPERIMENTAL_light_client_proof` for the receipt that ran
+    // it, and verifies that proof via `LightClient::verify_execution_proof`
+    // before trusting the return value it carries. That needs a signer and
+    // costs gas, which `plain_view_call` doesn't — the price of a result
+    // a malicious gateway can't fabricate. Falls back to `plain_view_call`
+    // when no light client is attached.
+    async fn verified_view_call(&self, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>, NovaError> {
+        let Some(light_client) = &self.light_client else {
+            return self.plain_view_call(method_name, args).await;
+        };
+
+        let outcome = self.execute_contract_call(method_name, args, None, 0).await?;
+        let receipt_outcome = outcome.receipts_outcome.first().ok_or_else(|| {
+            NovaError::ProofInvalid("transaction produced no receipt outcome to prove".to_string())
+        })?;
+
+        let request = light_client::execution_proof_request(
+            TransactionOrReceiptId::Receipt {
+                receipt_id: receipt_outcome.id,
+                receiver_id: self.contract_id.clone(),
+            },
+            light_client.head_hash(),
+        );
+        let proof = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        light_client.verify_execution_proof(&proof)?;
+
+        match &proof.outcome_proof.outcome.status {
+            ExecutionStatusView::SuccessValue(value) => Ok(value.clone()),
+            other => Err(NovaError::Near(format!("verified call did not succeed: {:?}", other))),
+        }
+    }
+
+    // Checks if a user is authorized in a group (read-only contract view).
+    // Merkle-proved against a light client when one is attached (see
+    // `verified_view_call`); otherwise a plain, gateway-trusted query.
+    pub async fn is_authorized(&self, group_id: &str, user_id: &str) -> Result<bool, NovaError> {
+        let args = serde_json::to_vec(&contract::IsAuthorizedArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let result = self.verified_view_call(contract::IS_AUTHORIZED_METHOD, args).await?;
+        let bool_result: contract::IsAuthorizedResult =
+            serde_json::from_slice(&result).map_err(|e| NovaError::Near(e.to_string()))?;
+        Ok(bool_result)
+    }
+
+    // Fetches the caller's own wrapped copy of the group key (read-only
+    // contract view) and unwraps it with the attached signer's private key
+    // via X25519 ECDH (see `group_key::unwrap_for_member`), so the plaintext
+    // data key only ever materializes client-side, in a zeroizing `Secret`.
+    pub async fn get_group_key(&self, group_id: &str, user_id: &str) -> Result<Secret, NovaError> {
+        let args = serde_json::to_vec(&contract::GetGroupKeyArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let result = self.verified_view_call(contract::GET_GROUP_KEY_METHOD, args).await?;
+        let wrapped_b64 = String::from_utf8(result).map_err(|e| NovaError::Near(e.to_string()))?;
+        let signer = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| NovaError::Signing("No signer attached to unwrap group key".to_string()))?;
+        group_key::unwrap_for_member(&wrapped_b64, signer.as_ref())
+    }
+
+    // Fetches the caller's wrapped copy of a group key as of `version`
+    // (read-only contract view), so a file sealed under an older version —
+    // before the group's most recent `store_group_key` rotation — can still
+    // be decrypted. See `composite_retrieve`, which looks up the right
+    // version from the matching `Transaction::key_version` before calling
+    // this instead of always fetching the latest.
+    pub async fn get_group_key_at_version(&self, group_id: &str, user_id: &str, version: u32) -> Result<Secret, NovaError> {
+        let args = serde_json::to_vec(&contract::GetGroupKeyAtVersionArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+            version,
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
         let request = methods::query::RpcQueryRequest {
-            block_reference: BlockReference::Finality(Finality::Final),
+            block_reference: self.read_finality.clone(),
             request: QueryRequest::CallFunction {
                 account_id: self.contract_id.clone(),
-                method_name: "get_group_key".to_string(),
+                method_name: contract::GET_GROUP_KEY_AT_VERSION_METHOD.to_string(),
                 args: args.into(),
             },
         };
-        let response = self.client.call(request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
         match response.kind {
             JsonRpcQueryResponseKind::CallResult(result) => {
-                let key_str = String::from_utf8(result.result).map_err(|e| NovaError::Near(e.to_string()))?;
-                Ok(key_str)
+                let wrapped_b64 = String::from_utf8(result.result).map_err(|e| NovaError::Near(e.to_string()))?;
+                let signer = self
+                    .signer
+                    .as_ref()
+                    .ok_or_else(|| NovaError::Signing("No signer attached to unwrap group key".to_string()))?;
+                group_key::unwrap_for_member(&wrapped_b64, signer.as_ref())
             }
             _ => Err(NovaError::Near("Invalid response kind".to_string())),
         }
@@ -141,47 +387,170 @@ impl NovaSdk {  // REMOVED generic type parameter
 
     // Fetches transactions for a group (authorized user view).
     pub async fn get_transactions_for_group(&self, group_id: &str, user_id: &str) -> Result<Vec<Transaction>, NovaError> {
-        let args = json!({"group_id": group_id, "user_id": user_id}).to_string().into_bytes();
+        let args = serde_json::to_vec(&contract::GetTransactionsForGroupArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let result = self.verified_view_call(contract::GET_TRANSACTIONS_FOR_GROUP_METHOD, args).await?;
+        let txs: contract::GetTransactionsForGroupResult =
+            serde_json::from_slice(&result).map_err(|e| NovaError::Near(format!("Failed to parse transactions: {}", e)))?;
+        Ok(txs)
+    }
+
+    // Current tip of the group's on-chain transaction hashchain (read-only
+    // contract view), hex-encoded. See `verify_transactions`, which
+    // recomputes this value client-side from the records themselves rather
+    // than trusting it on its own.
+    pub async fn get_group_head(&self, group_id: &str) -> Result<String, NovaError> {
+        let args = json!({"group_id": group_id}).to_string().into_bytes();
         let request = methods::query::RpcQueryRequest {
-            block_reference: BlockReference::Finality(Finality::Final),
+            block_reference: self.read_finality.clone(),
             request: QueryRequest::CallFunction {
                 account_id: self.contract_id.clone(),
-                method_name: "get_transactions_for_group".to_string(),
+                method_name: "get_group_head".to_string(),
                 args: args.into(),
             },
         };
-        let response = self.client.call(request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
         match response.kind {
             JsonRpcQueryResponseKind::CallResult(result) => {
-                let txs: Vec<Transaction> = serde_json::from_slice(&result.result)
-                    .map_err(|e| NovaError::Near(format!("Failed to parse transactions: {}", e)))?;
-                Ok(txs)
+                serde_json::from_slice(&result.result).map_err(|e| NovaError::Near(e.to_string()))
             }
             _ => Err(NovaError::Near("Invalid response kind".to_string())),
         }
     }
 
-    // Executes a signed function call on the contract.
+    // Independently re-derives a group's transaction hashchain client-side
+    // instead of trusting the contract's own `verify_group_chain` view — a
+    // compromised or dishonest contract could lie about its own check, but
+    // can't also fabricate records consistent with a head the client
+    // recomputed itself from `get_transactions_for_group` +
+    // `get_group_head`. Mirrors the contract's `insert_transaction`
+    // hashing exactly: `sha256(prev_head || user_id || file_hash ||
+    // ipfs_hash || recorded_at)`. Returns the index of the first record
+    // that breaks the chain, or `None` if every record agrees through to
+    // the current on-chain head.
+    pub async fn verify_transactions(&self, group_id: &str, user_id: &str) -> Result<Option<u64>, NovaError> {
+        let transactions = self.get_transactions_for_group(group_id, user_id).await?;
+        let head = self.get_group_head(group_id).await?;
+
+        let mut prev_head = [0u8; 32];
+        for (index, tx) in transactions.iter().enumerate() {
+            let expected = sha256_hash(format!(
+                "{}{}{}{}{}",
+                hex_encode(&prev_head),
+                tx.user_id,
+                tx.file_hash,
+                tx.ipfs_hash,
+                tx.recorded_at
+            ).as_bytes());
+            if hex_encode(&expected) != tx.chain_hash {
+                return Ok(Some(index as u64));
+            }
+            prev_head = expected;
+        }
+
+        if hex_encode(&prev_head) != head {
+            return Ok(Some(transactions.len() as u64));
+        }
+        Ok(None)
+    }
+
+    // Queries NEAR's `gas_price` RPC, caching the result keyed by the block
+    // height it was quoted at so repeated calls within the same block don't
+    // round-trip the network. A newer final block invalidates the cache.
+    pub async fn gas_price(&self) -> Result<Balance, NovaError> {
+        let block_request = methods::block::RpcBlockRequest {
+            block_reference: BlockReference::Finality(Finality::Final),
+        };
+        let block_response = self.client.call(block_request).await.map_err(NovaError::from_rpc_error)?;
+        let block_height = block_response.header.height;
+
+        {
+            let cache = self.gas_price_cache.lock().await;
+            if let Some((cached_height, price)) = *cache {
+                if cached_height >= block_height {
+                    return Ok(price);
+                }
+            }
+        }
+
+        let request = methods::gas_price::RpcGasPriceRequest {
+            block_id: Some(near_primitives::types::BlockId::Hash(block_response.header.hash)),
+        };
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        let price: Balance = response.gas_price;
+
+        let mut cache = self.gas_price_cache.lock().await;
+        *cache = Some((block_height, price));
+        Ok(price)
+    }
+
+    // Estimates the yoctoNEAR cost of calling `method` with `args`, i.e.
+    // `gas_limit * gas_price`, so callers can check affordability before
+    // signing. Uses `DEFAULT_GAS` as the limit unless the method itself
+    // attaches a tighter one.
+    pub async fn estimated_cost(&self, _method: &str, _args: &[u8]) -> Result<Balance, NovaError> {
+        let price = self.gas_price().await?;
+        Ok(price.saturating_mul(DEFAULT_GAS as u128))
+    }
+
+    // Executes a signed function call on the contract. `gas` defaults to
+    // `DEFAULT_GAS` when not supplied, so existing callers keep their old
+    // behavior while new code can override it per-call.
     async fn execute_contract_call(
         &self,
         method_name: &str,
         args: Vec<u8>,
-        gas: u64,
+        gas: Option<u64>,
         attached_deposit: u128,
     ) -> Result<FinalExecutionOutcomeView, NovaError> {
+        let gas = gas.unwrap_or(DEFAULT_GAS);
         let signer = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?;
 
-        let signer_account_id = match signer {
-            Signer::InMemory(s) => s.account_id.clone(),
-            _ => return Err(NovaError::Signing("Unsupported signer type".to_string())),
-        };
+        let signer_account_id = signer.account_id();
 
-        let public_key = match signer {
-            Signer::InMemory(s) => s.public_key.clone(),
-            _ => return Err(NovaError::Signing("Unsupported signer type".to_string())),
-        };
+        // Pre-flight: make sure the signer can actually cover gas + deposit
+        // before we broadcast, instead of finding out from a failed receipt.
+        let gas_price = self.gas_price().await?;
+        let required = gas_price.saturating_mul(gas as u128).saturating_add(attached_deposit);
+        let available = self.get_balance(signer_account_id.as_str()).await?;
+        if available < required {
+            return Err(NovaError::InsufficientBalance { required, available });
+        }
+
+        let actions = vec![Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: method_name.to_string(),
+            args,
+            gas,
+            deposit: attached_deposit,
+        }))];
+
+        let signed_tx = self.build_and_sign(signer.as_ref(), self.contract_id.clone(), actions).await?;
+
+        let broadcast_request = RpcBroadcastTxCommitRequest { signed_transaction: signed_tx };
+        let broadcast_response = self.client.call(broadcast_request).await.map_err(NovaError::from_rpc_error)?;
+
+        Ok(broadcast_response)
+    }
+
+    // Fetches a fresh nonce and block hash for `signer` and signs `actions`
+    // against `receiver_id`, mirroring the hash-then-sign construction
+    // `offline::sign_with` uses, since `Signer` trait objects aren't
+    // accepted by `SignedTransaction::from_actions` (that helper is built
+    // around `near_crypto::Signer` specifically). Shared by
+    // `execute_contract_call` and `execute_transfer` so the two don't drift.
+    async fn build_and_sign(
+        &self,
+        signer: &dyn Signer,
+        receiver_id: AccountId,
+        actions: Vec<Action>,
+    ) -> Result<SignedTransaction, NovaError> {
+        let signer_account_id = signer.account_id();
+        let public_key = signer.public_key();
 
-        // Fetch latest access key for nonce
         let access_key_request = methods::query::RpcQueryRequest {
             block_reference: BlockReference::Finality(Finality::Final),
             request: QueryRequest::ViewAccessKey {
@@ -189,92 +558,744 @@ impl NovaSdk {  // REMOVED generic type parameter
                 public_key: public_key.clone(),
             },
         };
-        let access_key_response = self.client.call(access_key_request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let access_key_response = self.client.call(access_key_request).await.map_err(NovaError::from_rpc_error)?;
         let access_key = match access_key_response.kind {
             JsonRpcQueryResponseKind::AccessKey(ak) => ak,
             _ => return Err(NovaError::Near("Invalid access key response".to_string())),
         };
         let nonce = access_key.nonce + 1;
 
-        // Fetch latest block hash
         let block_request = methods::block::RpcBlockRequest {
             block_reference: BlockReference::Finality(Finality::Final),
         };
-        let block_response = self.client.call(block_request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let block_response = self.client.call(block_request).await.map_err(NovaError::from_rpc_error)?;
         let block_hash: CryptoHash = block_response.header.hash;
-        let block_height: BlockHeight = block_response.header.height;
 
-        // Build transaction with FunctionCallAction
+        let unsigned = offline::build_unsigned(signer_account_id, public_key, receiver_id, actions, nonce, block_hash);
+        let envelope = offline::sign_with(&unsigned, signer)?;
+        SignedTxEnvelope::decode(envelope.as_str())
+    }
+
+    // Retries `execute_contract_call` on transient failures (timeout,
+    // rate-limit, a nonce that went stale between fetch and broadcast),
+    // re-fetching the nonce and block hash on each attempt. Fails fast on
+    // deterministic errors like a contract panic.
+    async fn execute_contract_call_with_retry(
+        &self,
+        method_name: &str,
+        args: Vec<u8>,
+        gas: Option<u64>,
+        attached_deposit: u128,
+    ) -> Result<FinalExecutionOutcomeView, NovaError> {
+        let mut attempts = 0;
+        loop {
+            match self
+                .execute_contract_call(method_name, args.clone(), gas, attached_deposit)
+                .await
+            {
+                Ok(outcome) => return Ok(outcome),
+                Err(e) if e.is_retryable() && attempts < 3 => {
+                    attempts += 1;
+                    sleep(Duration::from_secs(2u64.pow(attempts))).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // Fetches the access-key nonce (already incremented, ready to use) and
+    // the latest final block hash: the two pieces of state an air-gapped
+    // signer needs but can't fetch itself. Run this on an online host and
+    // carry the result to the offline one.
+    pub async fn fetch_nonce_and_block_hash(
+        &self,
+        account_id: &str,
+        public_key: &str,
+    ) -> Result<NonceAndBlockHash, NovaError> {
+        let account_id = AccountId::from_str(account_id).map_err(|_| NovaError::ParseAccount)?;
+        let public_key = near_crypto::PublicKey::from_str(public_key)
+            .map_err(|e| NovaError::Signing(e.to_string()))?;
+
+        let access_key_request = methods::query::RpcQueryRequest {
+            block_reference: BlockReference::Finality(Finality::Final),
+            request: QueryRequest::ViewAccessKey { account_id, public_key },
+        };
+        let access_key_response = self.client.call(access_key_request).await.map_err(NovaError::from_rpc_error)?;
+        let access_key = match access_key_response.kind {
+            JsonRpcQueryResponseKind::AccessKey(ak) => ak,
+            _ => return Err(NovaError::Near("Invalid access key response".to_string())),
+        };
+
+        let block_request = methods::block::RpcBlockRequest {
+            block_reference: BlockReference::Finality(Finality::Final),
+        };
+        let block_response = self.client.call(block_request).await.map_err(NovaError::from_rpc_error)?;
+
+        Ok(NonceAndBlockHash {
+            nonce: access_key.nonce + 1,
+            block_hash: block_response.header.hash,
+            block_height: block_response.header.height,
+        })
+    }
+
+    // Builds an unsigned function-call transaction against this SDK's
+    // contract. Supply `nonce_override`/`block_hash_override` when the
+    // caller already has them (e.g. carried in from an air-gapped signer's
+    // companion online host); otherwise they're fetched via
+    // `fetch_nonce_and_block_hash`.
+    pub async fn build_unsigned(
+        &self,
+        signer_id: &str,
+        public_key: &str,
+        method_name: &str,
+        args: Vec<u8>,
+        gas: Option<u64>,
+        deposit: u128,
+        nonce_override: Option<u64>,
+        block_hash_override: Option<CryptoHash>,
+    ) -> Result<UnsignedTx, NovaError> {
+        let signer_account = AccountId::from_str(signer_id).map_err(|_| NovaError::ParseAccount)?;
+        let public_key_parsed = near_crypto::PublicKey::from_str(public_key)
+            .map_err(|e| NovaError::Signing(e.to_string()))?;
+
+        let (nonce, block_hash) = match (nonce_override, block_hash_override) {
+            (Some(nonce), Some(block_hash)) => (nonce, block_hash),
+            _ => {
+                let fetched = self.fetch_nonce_and_block_hash(signer_id, public_key).await?;
+                (
+                    nonce_override.unwrap_or(fetched.nonce),
+                    block_hash_override.unwrap_or(fetched.block_hash),
+                )
+            }
+        };
+
         let actions = vec![Action::FunctionCall(Box::new(FunctionCallAction {
             method_name: method_name.to_string(),
             args,
-            gas,
-            deposit: attached_deposit,
+            gas: gas.unwrap_or(DEFAULT_GAS),
+            deposit,
         }))];
 
-        // Use SignedTransaction::from_actions to construct the transaction
-        let signed_tx = SignedTransaction::from_actions(
-            nonce,
-            signer_account_id,
+        Ok(offline::build_unsigned(
+            signer_account,
+            public_key_parsed,
             self.contract_id.clone(),
-            signer,
             actions,
+            nonce,
             block_hash,
-            block_height,
-        );
+        ))
+    }
 
-        let broadcast_request = RpcBroadcastTxCommitRequest { signed_transaction: signed_tx };
-        let broadcast_response = self.client.call(broadcast_request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+    // Signs an unsigned transaction using this instance's attached signer,
+    // entirely in memory. Safe to call on a host with no network access at
+    // all; the resulting envelope is carried to an online instance for
+    // `broadcast_signed`.
+    pub fn sign_offline(&self, unsigned: &UnsignedTx) -> Result<SignedTxEnvelope, NovaError> {
+        let signer = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?;
+        offline::sign_with(unsigned, signer.as_ref())
+    }
 
-        Ok(broadcast_response)
+    // Submits a pre-signed, base64-encoded transaction produced by
+    // `sign_offline`. Requires no signer of its own, since the signature
+    // was already produced offline.
+    pub async fn broadcast_signed(&self, signed_tx_b64: &str) -> Result<String, NovaError> {
+        let signed_transaction = SignedTxEnvelope::decode(signed_tx_b64)?;
+        let broadcast_request = RpcBroadcastTxCommitRequest { signed_transaction };
+        let outcome = self.client.call(broadcast_request).await.map_err(NovaError::from_rpc_error)?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    // One-shot `build_unsigned` + `sign_offline` for a fully air-gapped
+    // host: `nonce` and `block_hash` must be supplied up front (carried in
+    // from an online host's `fetch_nonce_and_block_hash`), so this never
+    // touches the network. Covers `store_group_key`, `record_transaction`,
+    // `revoke_group_member`, `composite_upload`, or any other contract
+    // method by name, the same way `execute_contract_call` does online.
+    pub async fn sign_only(
+        &self,
+        signer_id: &str,
+        public_key: &str,
+        method_name: &str,
+        args: Vec<u8>,
+        gas: Option<u64>,
+        deposit: u128,
+        nonce: Nonce,
+        block_hash: CryptoHash,
+    ) -> Result<SignedTxEnvelope, NovaError> {
+        let unsigned = self
+            .build_unsigned(signer_id, public_key, method_name, args, gas, deposit, Some(nonce), Some(block_hash))
+            .await?;
+        self.sign_offline(&unsigned)
     }
 
     // Registers a new group (owner-only, payable).
     pub async fn register_group(&self, group_id: &str) -> Result<String, NovaError> {
-        let args = json!({"group_id": group_id}).to_string().into_bytes();
-        let outcome = self.execute_contract_call("register_group", args, 300_000_000_000_000, 100_000_000_000_000_000_000_000).await?;
+        let args = serde_json::to_vec(&contract::RegisterGroupArgs { group_id: group_id.to_string() })
+            .map_err(|e| NovaError::Near(e.to_string()))?;
+        let outcome =
+            self.execute_contract_call(contract::REGISTER_GROUP_METHOD, args, None, 100_000_000_000_000_000_000_000).await?;
         self.parse_outcome(&outcome.transaction_outcome.outcome)
     }
 
-    // Adds a member to a group (owner-only, payable).
-    pub async fn add_group_member(&self, group_id: &str, user_id: &str) -> Result<String, NovaError> {
-        let args = json!({"group_id": group_id, "user_id": user_id}).to_string().into_bytes();
-        let outcome = self.execute_contract_call("add_group_member", args, 300_000_000_000_000, 500_000_000_000_000_000).await?;
+    // Checks whether a group has been registered (read-only contract
+    // view). Unlike `is_authorized`/`get_transactions_for_group` this
+    // doesn't need Merkle-proving against a light client — a malicious
+    // gateway claiming a real group doesn't exist is an availability
+    // concern, not one this SDK's trust model covers elsewhere either.
+    pub async fn groups_contains_key(&self, group_id: &str) -> Result<bool, NovaError> {
+        let args = serde_json::to_vec(&contract::GroupsContainsKeyArgs { group_id: group_id.to_string() })
+            .map_err(|e| NovaError::Near(e.to_string()))?;
+        let request = methods::query::RpcQueryRequest {
+            block_reference: self.read_finality.clone(),
+            request: QueryRequest::CallFunction {
+                account_id: self.contract_id.clone(),
+                method_name: contract::GROUPS_CONTAINS_KEY_METHOD.to_string(),
+                args: args.into(),
+            },
+        };
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
+        match response.kind {
+            JsonRpcQueryResponseKind::CallResult(result) => {
+                serde_json::from_slice(&result.result).map_err(|e| NovaError::Near(e.to_string()))
+            }
+            _ => Err(NovaError::Near("Invalid response kind".to_string())),
+        }
+    }
+
+    // Adds a member to a group (owner-only, payable). Set `validate_only`
+    // to parse the member's account ID and check they aren't already a
+    // member without broadcasting anything.
+    pub async fn add_group_member(&self, group_id: &str, user_id: &str, validate_only: bool) -> Result<String, NovaError> {
+        AccountId::from_str(user_id).map_err(|_| NovaError::ParseAccount)?;
+        if validate_only {
+            if self.is_authorized(group_id, user_id).await? {
+                return Err(NovaError::Validation(format!("{} is already a member of {}", user_id, group_id)));
+            }
+            return Ok("validated".to_string());
+        }
+        let args = serde_json::to_vec(&contract::AddGroupMemberArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let outcome =
+            self.execute_contract_call(contract::ADD_GROUP_MEMBER_METHOD, args, None, 500_000_000_000_000_000).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    // Revokes a member from a group (owner-only, payable, rotates key). Set
+    // `validate_only` to check current membership without broadcasting.
+    pub async fn revoke_group_member(&self, group_id: &str, user_id: &str, validate_only: bool) -> Result<String, NovaError> {
+        AccountId::from_str(user_id).map_err(|_| NovaError::ParseAccount)?;
+        if validate_only {
+            if !self.is_authorized(group_id, user_id).await? {
+                return Err(NovaError::Validation(format!("{} is not a member of {}", user_id, group_id)));
+            }
+            return Ok("validated".to_string());
+        }
+        let args = serde_json::to_vec(&contract::RevokeGroupMemberArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let outcome =
+            self.execute_contract_call(contract::REVOKE_GROUP_MEMBER_METHOD, args, None, 500_000_000_000_000_000).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    // Registers (or rotates) the ed25519 key `record_transaction_signed`
+    // will verify `user_id`'s relayed requests against (owner-only,
+    // payable). `public_key` is that member's own NEAR public key string
+    // (e.g. `"ed25519:..."`) — ordinarily the key backing their access
+    // key, so they can sign a request with the same key they already
+    // hold without minting anything new.
+    pub async fn register_agent_key(&self, group_id: &str, user_id: &str, public_key: &str) -> Result<String, NovaError> {
+        let args = json!({"group_id": group_id, "user_id": user_id, "public_key": public_key}).to_string().into_bytes();
+        let outcome = self.execute_contract_call("register_agent_key", args, None, 500_000_000_000_000_000).await?;
         self.parse_outcome(&outcome.transaction_outcome.outcome)
     }
 
-    // Revokes a member from a group (owner-only, payable, rotates key).
-    pub async fn revoke_group_member(&self, group_id: &str, user_id: &str) -> Result<String, NovaError> {
-        let args = json!({"group_id": group_id, "user_id": user_id}).to_string().into_bytes();
-        let outcome = self.execute_contract_call("revoke_group_member", args, 300_000_000_000_000, 500_000_000_000_000_000).await?;
+    // Grants `role` to `account_id` (`Admin`-only on the contract side), so
+    // a deployment can hand group management, key custody, or role
+    // administration to separate operator keys instead of funneling every
+    // privileged call through one.
+    pub async fn grant_role(&self, account_id: &str, role: Role) -> Result<String, NovaError> {
+        let args = json!({"account_id": account_id, "role": role}).to_string().into_bytes();
+        let outcome = self.execute_contract_call("grant_role", args, None, 500_000_000_000_000_000).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    // Revokes `role` from `account_id` (`Admin`-only on the contract side).
+    pub async fn revoke_role(&self, account_id: &str, role: Role) -> Result<String, NovaError> {
+        let args = json!({"account_id": account_id, "role": role}).to_string().into_bytes();
+        let outcome = self.execute_contract_call("revoke_role", args, None, 500_000_000_000_000_000).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    pub async fn has_role(&self, account_id: &str, role: Role) -> Result<bool, NovaError> {
+        let args = json!({"account_id": account_id, "role": role}).to_string().into_bytes();
+        let request = methods::query::RpcQueryRequest {
+            block_reference: self.read_finality.clone(),
+            request: QueryRequest::CallFunction {
+                account_id: self.contract_id.clone(),
+                method_name: "has_role".to_string(),
+                args: args.into(),
+            },
+        };
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
+        match response.kind {
+            JsonRpcQueryResponseKind::CallResult(result) => {
+                serde_json::from_slice(&result.result).map_err(|e| NovaError::Near(e.to_string()))
+            }
+            _ => Err(NovaError::Near("Invalid response kind".to_string())),
+        }
+    }
+
+    pub async fn roles_of(&self, account_id: &str) -> Result<Vec<Role>, NovaError> {
+        let args = json!({"account_id": account_id}).to_string().into_bytes();
+        let request = methods::query::RpcQueryRequest {
+            block_reference: self.read_finality.clone(),
+            request: QueryRequest::CallFunction {
+                account_id: self.contract_id.clone(),
+                method_name: "roles_of".to_string(),
+                args: args.into(),
+            },
+        };
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
+        match response.kind {
+            JsonRpcQueryResponseKind::CallResult(result) => {
+                serde_json::from_slice(&result.result).map_err(|e| NovaError::Near(e.to_string()))
+            }
+            _ => Err(NovaError::Near("Invalid response kind".to_string())),
+        }
+    }
+
+    // Signs a `record_transaction` request as `user_id` with the attached
+    // signer and relays it through `record_transaction_signed`, so an
+    // agent holding only its own NEAR access key — not the group owner's —
+    // can submit records on a member's behalf. The signer must hold the
+    // private half of whatever key `register_agent_key` registered for
+    // `user_id`. `nonce` must not have been used for `user_id` before;
+    // the caller owns tracking its own next nonce.
+    pub async fn record_transaction_as_agent(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        file_hash: &str,
+        ipfs_hash: &str,
+        nonce: u64,
+    ) -> Result<String, NovaError> {
+        validation::validate_hex(file_hash, 32)?;
+        validation::validate_cid(ipfs_hash)?;
+        let signer = self.signer.as_ref().ok_or_else(|| NovaError::Signing("No signer attached".to_string()))?;
+        let message = format!("{}{}{}{}{}", group_id, user_id, file_hash, ipfs_hash, nonce);
+        let digest = sha256_hash(message.as_bytes());
+        let signature = match signer.sign(&digest) {
+            near_crypto::Signature::ED25519(sig) => sig.to_bytes().to_vec(),
+            _ => return Err(NovaError::Signing("record_transaction_signed requires an ed25519 member key".to_string())),
+        };
+        let args = json!({
+            "group_id": group_id,
+            "user_id": user_id,
+            "file_hash": file_hash,
+            "ipfs_hash": ipfs_hash,
+            "nonce": nonce,
+            "signature": general_purpose::STANDARD.encode(signature),
+        })
+        .to_string()
+        .into_bytes();
+        let outcome = self.execute_contract_call("record_transaction_signed", args, None, 2_000_000_000_000_000_000).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    // Replaces the group's entire member_id -> wrapped_key map (owner-only,
+    // payable) with `wrapped_keys`, each entry already produced by
+    // `group_key::wrap_for_member`. Distributing a key and rotating it are
+    // the same call — the contract just stores whatever map it's handed.
+    // Set `validate_only` to stop before broadcasting.
+    pub async fn store_group_key(
+        &self,
+        group_id: &str,
+        wrapped_keys: &[(String, String)],
+        validate_only: bool,
+    ) -> Result<String, NovaError> {
+        if validate_only {
+            return Ok("validated".to_string());
+        }
+        let args = serde_json::to_vec(&contract::StoreGroupKeyArgs {
+            group_id: group_id.to_string(),
+            wrapped_keys: wrapped_keys.to_vec(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let outcome =
+            self.execute_contract_call(contract::STORE_GROUP_KEY_METHOD, args, None, 500_000_000_000_000_000).await?;
         self.parse_outcome(&outcome.transaction_outcome.outcome)
     }
 
-    // Stores a base64 group key (owner-only, payable).
-    pub async fn store_group_key(&self, group_id: &str, key_b64: &str) -> Result<String, NovaError> {
-        let args = json!({"group_id": group_id, "key": key_b64}).to_string().into_bytes();
-        let outcome = self.execute_contract_call("store_group_key", args, 300_000_000_000_000, 500_000_000_000_000_000).await?;
+    // Wraps `data_key` for each `(user_id, member_public_key)` pair via
+    // `group_key::wrap_for_member` and stores the resulting map in one
+    // `store_group_key` call. `member_public_key` is each member's NEAR
+    // public key string (e.g. `"ed25519:..."`); only ed25519 members can
+    // receive a wrapped key today (see `Signer::ecdh_shared_secret`).
+    pub async fn distribute_group_key(
+        &self,
+        group_id: &str,
+        data_key: &Secret,
+        members: &[(&str, &str)],
+        validate_only: bool,
+    ) -> Result<String, NovaError> {
+        let mut data_key_bytes = [0u8; 32];
+        if data_key.expose_bytes().len() != 32 {
+            return Err(NovaError::InvalidKey);
+        }
+        data_key_bytes.copy_from_slice(data_key.expose_bytes());
+
+        let mut wrapped_keys = Vec::with_capacity(members.len());
+        for (user_id, member_public_key) in members {
+            let public_key = near_crypto::PublicKey::from_str(member_public_key)
+                .map_err(|e| NovaError::Signing(e.to_string()))?;
+            let wrapped = group_key::wrap_for_member(&data_key_bytes, &public_key)?;
+            wrapped_keys.push((user_id.to_string(), wrapped));
+        }
+        self.store_group_key(group_id, &wrapped_keys, validate_only).await
+    }
+
+    // Splits `data_key` into one Shamir share per member (see
+    // `shamir::split_secret`), wraps each member's share the same way
+    // `distribute_group_key` wraps a whole key, and stores the wrapped
+    // shares plus `threshold` and a commitment to `data_key` in one
+    // `split_group_key` call. Unlike `distribute_group_key`, no single
+    // member's copy (nor any subset smaller than `threshold`) is enough to
+    // recover the key on its own. Set `validate_only` to stop before
+    // broadcasting.
+    pub async fn distribute_group_key_split(
+        &self,
+        group_id: &str,
+        data_key: &Secret,
+        threshold: u32,
+        members: &[(&str, &str)],
+        validate_only: bool,
+    ) -> Result<String, NovaError> {
+        let mut data_key_bytes = [0u8; 32];
+        if data_key.expose_bytes().len() != 32 {
+            return Err(NovaError::InvalidKey);
+        }
+        data_key_bytes.copy_from_slice(data_key.expose_bytes());
+
+        let x_indices: Vec<u8> = (1..=members.len() as u8).collect();
+        let shares = shamir::split_secret(&data_key_bytes, threshold as u8, &x_indices)?;
+
+        let mut wrapped_shares = Vec::with_capacity(members.len());
+        for ((user_id, member_public_key), (&x_index, share)) in members.iter().zip(x_indices.iter().zip(shares.iter())) {
+            let public_key = near_crypto::PublicKey::from_str(member_public_key)
+                .map_err(|e| NovaError::Signing(e.to_string()))?;
+            let wrapped = group_key::wrap_for_member(share, &public_key)?;
+            wrapped_shares.push((user_id.to_string(), x_index, wrapped));
+        }
+        let commitment = hex_encode(&sha256_hash(&data_key_bytes));
+        self.split_group_key(group_id, threshold, &wrapped_shares, &commitment, validate_only).await
+    }
+
+    // Stores a Shamir-split key version (owner-only, payable): `shares` is
+    // each member's own wrapped share, already produced by
+    // `distribute_group_key_split`. Set `validate_only` to stop before
+    // broadcasting.
+    pub async fn split_group_key(
+        &self,
+        group_id: &str,
+        threshold: u32,
+        shares: &[(String, u8, String)],
+        commitment: &str,
+        validate_only: bool,
+    ) -> Result<String, NovaError> {
+        if validate_only {
+            return Ok("validated".to_string());
+        }
+        let args = json!({"group_id": group_id, "threshold": threshold, "shares": shares, "commitment": commitment})
+            .to_string()
+            .into_bytes();
+        let outcome = self.execute_contract_call("split_group_key", args, None, 500_000_000_000_000_000).await?;
         self.parse_outcome(&outcome.transaction_outcome.outcome)
     }
 
+    // Fetches the threshold and secret commitment for a Shamir-split
+    // `version` (read-only contract view), so `reconstruct_group_key` can
+    // confirm a reconstructed secret is the right one before it's trusted.
+    pub async fn get_group_key_commitment(&self, group_id: &str, version: u32) -> Result<(u32, String), NovaError> {
+        let args = json!({"group_id": group_id, "version": version}).to_string().into_bytes();
+        let request = methods::query::RpcQueryRequest {
+            block_reference: self.read_finality.clone(),
+            request: QueryRequest::CallFunction {
+                account_id: self.contract_id.clone(),
+                method_name: "get_group_key_commitment".to_string(),
+                args: args.into(),
+            },
+        };
+        let response = self.client.call(request).await.map_err(NovaError::from_rpc_error)?;
+        self.verify_trustless(response.block_height)?;
+        match response.kind {
+            JsonRpcQueryResponseKind::CallResult(result) => {
+                let (threshold, commitment): (u32, String) =
+                    serde_json::from_slice(&result.result).map_err(|e| NovaError::Near(e.to_string()))?;
+                Ok((threshold, commitment))
+            }
+            _ => Err(NovaError::Near("Invalid response kind".to_string())),
+        }
+    }
+
+    // Reconstructs a Shamir-split group key from `threshold`-many members'
+    // own decrypted shares (each already unwrapped via
+    // `group_key::unwrap_for_member`) and checks the result against
+    // `commitment` from `get_group_key_commitment` before returning it —
+    // a wrong or insufficient subset of shares silently produces garbage
+    // (see `shamir::reconstruct_secret`), so this is the only point that
+    // actually confirms success.
+    pub fn reconstruct_group_key(shares: &[(u8, Secret)], commitment: &str) -> Result<Secret, NovaError> {
+        let mut byte_shares = Vec::with_capacity(shares.len());
+        for (x_index, share) in shares {
+            if share.expose_bytes().len() != 32 {
+                return Err(NovaError::InvalidKey);
+            }
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(share.expose_bytes());
+            byte_shares.push((*x_index, bytes));
+        }
+        let secret = shamir::reconstruct_secret(&byte_shares)?;
+        if hex_encode(&sha256_hash(&secret)) != commitment {
+            return Err(NovaError::Validation("reconstructed key does not match the on-chain commitment".to_string()));
+        }
+        Ok(Secret::new(secret.to_vec()))
+    }
+
+    // Re-wraps every file's DEK for `group_id` from `old_key` to `new_key`
+    // and re-uploads the re-wrapped envelopes to IPFS — only the small
+    // wrapped-DEK header changes, so this never re-encrypts (or even
+    // touches) a file body. Distributes `new_key` to `members` once all
+    // envelopes are rewrapped. Returns the (old_cid, new_cid) pairs: the
+    // contract has no API to update a recorded `ipfs_hash` in place, so
+    // the caller is responsible for reconciling its own index against the
+    // new CIDs.
+    pub async fn rotate_group_key(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        old_key: &Secret,
+        new_key: &Secret,
+        members: &[(&str, &str)],
+    ) -> Result<Vec<(String, String)>, NovaError> {
+        let transactions = self.get_transactions_for_group(group_id, user_id).await?;
+        let mut rotated = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            let envelope_b64 = self.ipfs_retrieve(&tx.ipfs_hash).await?;
+            let rewrapped_b64 = self.rewrap_envelope(&envelope_b64, old_key, new_key)?;
+            let new_cid = self.ipfs_upload(&rewrapped_b64, &tx.ipfs_hash).await?;
+            rotated.push((tx.ipfs_hash, new_cid));
+        }
+        self.distribute_group_key(group_id, new_key, members, false).await?;
+        Ok(rotated)
+    }
+
+    // Revokes `revoked_user_id` then immediately rotates the group's data
+    // key, so the revoked member's cached copy stops decrypting anything
+    // going forward: generates a fresh data key, re-wraps every file's DEK
+    // under it (`rotate_group_key`), and distributes it only to
+    // `remaining_members`. This is what gives revocation real forward
+    // secrecy instead of just removing future authorization.
+    pub async fn revoke_and_rotate(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        revoked_user_id: &str,
+        old_key: &Secret,
+        remaining_members: &[(&str, &str)],
+    ) -> Result<Vec<(String, String)>, NovaError> {
+        self.revoke_group_member(group_id, revoked_user_id, false).await?;
+        let new_key = Secret::new(group_key::generate_data_key().to_vec());
+        self.rotate_group_key(group_id, user_id, old_key, &new_key, remaining_members).await
+    }
+
+    // Same as `rotate_group_key`, but distributes `new_key` via Shamir
+    // split (`distribute_group_key_split`) instead of wrapping it whole
+    // for every member.
+    pub async fn rotate_group_key_split(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        old_key: &Secret,
+        new_key: &Secret,
+        threshold: u32,
+        members: &[(&str, &str)],
+    ) -> Result<Vec<(String, String)>, NovaError> {
+        let transactions = self.get_transactions_for_group(group_id, user_id).await?;
+        let mut rotated = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            let envelope_b64 = self.ipfs_retrieve(&tx.ipfs_hash).await?;
+            let rewrapped_b64 = self.rewrap_envelope(&envelope_b64, old_key, new_key)?;
+            let new_cid = self.ipfs_upload(&rewrapped_b64, &tx.ipfs_hash).await?;
+            rotated.push((tx.ipfs_hash, new_cid));
+        }
+        self.distribute_group_key_split(group_id, new_key, threshold, members, false).await?;
+        Ok(rotated)
+    }
+
+    // Revokes `revoked_user_id` then immediately re-splits the group's
+    // data key under a fresh secret (see `rotate_group_key_split`), so the
+    // revoked member's cached share stops being useful even pooled with
+    // `threshold - 1` other shares.
+    pub async fn revoke_and_split_rotate(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        revoked_user_id: &str,
+        old_key: &Secret,
+        threshold: u32,
+        remaining_members: &[(&str, &str)],
+    ) -> Result<Vec<(String, String)>, NovaError> {
+        self.revoke_group_member(group_id, revoked_user_id, false).await?;
+        let new_key = Secret::new(group_key::generate_data_key().to_vec());
+        self.rotate_group_key_split(group_id, user_id, old_key, &new_key, threshold, remaining_members).await
+    }
+
     // Records a file transaction (owner-only, payable, returns trans_id).
-    pub async fn record_transaction(&self, group_id: &str, user_id: &str, file_hash: &str, ipfs_hash: &str) -> Result<String, NovaError> {
-        let args = json!({"group_id": group_id, "user_id": user_id, "file_hash": file_hash, "ipfs_hash": ipfs_hash}).to_string().into_bytes();
-        let outcome = self.execute_contract_call("record_transaction", args, 300_000_000_000_000, 2_000_000_000_000_000_000).await?;
+    // `gas_limit` overrides the default gas attached to the call; pass
+    // `None` to use `DEFAULT_GAS`. Runs the same checks the contract would
+    // (CID format, hex file hash, group authorization) locally first; set
+    // `validate_only` to stop there instead of broadcasting.
+    pub async fn record_transaction(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        file_hash: &str,
+        ipfs_hash: &str,
+        gas_limit: Option<u64>,
+        validate_only: bool,
+    ) -> Result<String, NovaError> {
+        if !validate_only {
+            self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?;
+        }
+        validation::validate_hex(file_hash, 32)?;
+        validation::validate_cid(ipfs_hash)?;
+        if !self.is_authorized(group_id, user_id).await? {
+            return Err(NovaError::Validation(format!("{} is not authorized in group {}", user_id, group_id)));
+        }
+        if validate_only {
+            return Ok("validated".to_string());
+        }
+
+        let args = serde_json::to_vec(&contract::RecordTransactionArgs {
+            group_id: group_id.to_string(),
+            user_id: user_id.to_string(),
+            file_hash: file_hash.to_string(),
+            ipfs_hash: ipfs_hash.to_string(),
+        })
+        .map_err(|e| NovaError::Near(e.to_string()))?;
+        let outcome = self
+            .execute_contract_call_with_retry(contract::RECORD_TRANSACTION_METHOD, args, gas_limit, 2_000_000_000_000_000_000)
+            .await?;
         match self.parse_outcome_detailed(&outcome.transaction_outcome.outcome) {
             Ok(value) => Ok(value),
             Err(_) => self.parse_outcome(&outcome.transaction_outcome.outcome),
         }
     }
 
-    // Transfers tokens to another account (signed transfer action).
-    pub async fn transfer_tokens(&self, to_account: &str, amount_yocto: u128) -> Result<String, NovaError> {
+    // Transfers tokens to another account (signed transfer action). Set
+    // `validate_only` to check the signer's balance covers `amount_yocto`
+    // plus `TRANSFER_BALANCE_RESERVE` without broadcasting anything.
+    pub async fn transfer_tokens(&self, to_account: &str, amount_yocto: u128, validate_only: bool) -> Result<String, NovaError> {
         let to_id = AccountId::from_str(to_account).map_err(|_| NovaError::ParseAccount)?;
+
+        if validate_only {
+            let signer = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?;
+            let available = self.get_balance(signer.account_id().as_str()).await?;
+            let required = amount_yocto.saturating_add(TRANSFER_BALANCE_RESERVE);
+            if available < required {
+                return Err(NovaError::InsufficientBalance { required, available });
+            }
+            return Ok("validated".to_string());
+        }
+
         let actions = vec![Action::Transfer(TransferAction { deposit: amount_yocto })];
         let outcome = self.execute_transfer(to_id, actions).await?;
         self.parse_outcome(&outcome.transaction_outcome.outcome)
     }
 
+    // Adds a function-call access key scoped to `receiver_id`, so a server
+    // or delegate can sign calls to specific methods with a narrowly-
+    // permissioned key while the account's full-access key stays offline.
+    // Mirrors Aurora's "add access key" transaction type. NEAR only allows
+    // an `AddKey` action against the signer's own account, so `account_id`
+    // is checked against the attached signer rather than trusted blindly —
+    // this is what turns a typo'd `account_id` into an early
+    // `NovaError::Validation` instead of a remote transaction failure.
+    pub async fn add_function_call_key(
+        &self,
+        account_id: &str,
+        public_key: PublicKey,
+        allowance: Option<u128>,
+        receiver_id: &str,
+        method_names: Vec<String>,
+    ) -> Result<String, NovaError> {
+        let signer_account_id = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?.account_id();
+        let requested_account_id = AccountId::from_str(account_id).map_err(|_| NovaError::ParseAccount)?;
+        if requested_account_id != signer_account_id {
+            return Err(NovaError::Validation(format!(
+                "can only add an access key to the signer's own account ({}), not {}",
+                signer_account_id, requested_account_id
+            )));
+        }
+        let access_key = AccessKey {
+            nonce: 0,
+            permission: AccessKeyPermission::FunctionCall(FunctionCallPermission {
+                allowance,
+                receiver_id: receiver_id.to_string(),
+                method_names,
+            }),
+        };
+        let actions = vec![Action::AddKey(Box::new(AddKeyAction { public_key, access_key }))];
+        let outcome = self.execute_transfer(signer_account_id, actions).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
+    // The two contract methods a group member needs to participate —
+    // upload/record a file and fetch their own wrapped group key — and
+    // nothing else, so a delegated key can never drain the account or
+    // touch group membership.
+    const GROUP_MEMBER_METHODS: &[&str] = &["record_transaction", "get_group_key"];
+
+    // Provisions a least-privilege delegate key for group participation:
+    // `add_function_call_key` scoped to `self.contract_id` and
+    // `Self::GROUP_MEMBER_METHODS`, so a group admin can hand a member a
+    // key that can upload/record within the group without ever holding a
+    // key that could drain the account or change membership.
+    pub async fn provision_group_member_key(
+        &self,
+        public_key: PublicKey,
+        allowance: Option<u128>,
+    ) -> Result<String, NovaError> {
+        let signer_account_id = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?.account_id();
+        self.add_function_call_key(
+            signer_account_id.as_str(),
+            public_key,
+            allowance,
+            self.contract_id.as_str(),
+            Self::GROUP_MEMBER_METHODS.iter().map(|s| s.to_string()).collect(),
+        )
+        .await
+    }
+
+    // Deletes an access key (full-access or function-call) from the
+    // signer's own account, e.g. to revoke a compromised or retired
+    // server-side function-call key added via `add_function_call_key`.
+    pub async fn delete_key(&self, public_key: PublicKey) -> Result<String, NovaError> {
+        let signer_account_id = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?.account_id();
+        let actions = vec![Action::DeleteKey(Box::new(DeleteKeyAction { public_key }))];
+        let outcome = self.execute_transfer(signer_account_id, actions).await?;
+        self.parse_outcome(&outcome.transaction_outcome.outcome)
+    }
+
     async fn execute_transfer(
         &self,
         to_id: AccountId,
@@ -282,50 +1303,10 @@ impl NovaSdk {  // REMOVED generic type parameter
     ) -> Result<FinalExecutionOutcomeView, NovaError> {
         let signer = self.signer.as_ref().ok_or(NovaError::Signing("No signer attached".to_string()))?;
 
-        let signer_account_id = match signer {
-            Signer::InMemory(s) => s.account_id.clone(),
-            _ => return Err(NovaError::Signing("Unsupported signer type".to_string())),
-        };
-
-        let public_key = match signer {
-            Signer::InMemory(s) => s.public_key.clone(),
-            _ => return Err(NovaError::Signing("Unsupported signer type".to_string())),
-        };
-
-        // Fetch nonce and block hash
-        let access_key_request = methods::query::RpcQueryRequest {
-            block_reference: BlockReference::Finality(Finality::Final),
-            request: QueryRequest::ViewAccessKey {
-                account_id: signer_account_id.clone(),
-                public_key: public_key.clone(),
-            },
-        };
-        let access_key_response = self.client.call(access_key_request).await.map_err(|e| NovaError::Near(e.to_string()))?;
-        let access_key = match access_key_response.kind {
-            JsonRpcQueryResponseKind::AccessKey(ak) => ak,
-            _ => return Err(NovaError::Near("Invalid access key response".to_string())),
-        };
-        let nonce = access_key.nonce + 1;
-
-        let block_request = methods::block::RpcBlockRequest {
-            block_reference: BlockReference::Finality(Finality::Final),
-        };
-        let block_response = self.client.call(block_request).await.map_err(|e| NovaError::Near(e.to_string()))?;
-        let block_hash: CryptoHash = block_response.header.hash;
-        let block_height: BlockHeight = block_response.header.height;
-
-        let signed_tx = SignedTransaction::from_actions(
-            nonce,
-            signer_account_id,
-            to_id,
-            signer,
-            actions,
-            block_hash,
-            block_height,
-        );
+        let signed_tx = self.build_and_sign(signer.as_ref(), to_id, actions).await?;
 
         let broadcast_request = RpcBroadcastTxCommitRequest { signed_transaction: signed_tx };
-        let broadcast_response = self.client.call(broadcast_request).await.map_err(|e| NovaError::Near(e.to_string()))?;
+        let broadcast_response = self.client.call(broadcast_request).await.map_err(NovaError::from_rpc_error)?;
 
         Ok(broadcast_response)
     }
@@ -359,21 +1340,114 @@ impl NovaSdk {  // REMOVED generic type parameter
         data: &[u8],
         filename: &str,
     ) -> Result<CompositeUploadResult, NovaError> {
+        // Step 0: Check authorization before spending any work on
+        // encryption or an IPFS upload the final `record_transaction` would
+        // reject anyway.
+        if !self.is_authorized(group_id, user_id).await? {
+            return Err(NovaError::Validation(format!("{} is not authorized in group {}", user_id, group_id)));
+        }
+
         // Step 1: Fetch group key
-        let key_b64 = self.get_group_key(group_id, user_id).await?;
-        
-        // Step 2: Encrypt data
-        let encrypted_b64 = self.encrypt_data(data, &key_b64)?;
-        
+        let key = self.get_group_key(group_id, user_id).await?;
+
+        // Step 2: Envelope-encrypt under a fresh per-file DEK, wrapped by
+        // the group key, so a future key rotation only re-wraps the DEK
+        // instead of re-encrypting the file body.
+        let encrypted_b64 = self.envelope_encrypt(data, &key)?;
+
         // Step 3: Upload to IPFS
         let cid = self.ipfs_upload(&encrypted_b64, filename).await?;
-        
+
         // Step 4: Calculate file hash from original data
         let file_hash = hex_encode(&sha256_hash(data));
-        
+
         // Step 5: Record transaction on blockchain
-        let trans_id = self.record_transaction(group_id, user_id, &file_hash, &cid).await?;
-        
+        let trans_id = self.record_transaction(group_id, user_id, &file_hash, &cid, None, false).await?;
+
+        Ok(CompositeUploadResult {
+            cid,
+            trans_id,
+            file_hash,
+        })
+    }
+
+    // Streaming counterpart of `composite_upload`: reads `reader` in fixed
+    // `CHUNK_SIZE` pieces, encrypting and hashing each one in place instead
+    // of materializing the whole file, then streams ciphertext straight
+    // into the Pinata request body as it's produced. Encrypts under a
+    // fresh per-file DEK wrapped by the group key — the same `MAGIC ||
+    // VERSION || wrapped_dek_len || wrapped_dek || IV || ciphertext`
+    // envelope `envelope_encrypt` builds in one pass — so the result is
+    // retrievable through `composite_retrieve`/`composite_retrieve_stream_unverified`
+    // interchangeably with a non-streamed upload.
+    pub async fn composite_upload_stream(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        mut reader: impl tokio::io::AsyncRead + Unpin + Send + 'static,
+        filename: &str,
+    ) -> Result<CompositeUploadResult, NovaError> {
+        use tokio::io::AsyncReadExt;
+
+        let group_key = self.get_group_key(group_id, user_id).await?;
+
+        let mut dek = [0u8; 32];
+        use rand::RngCore;
+        rand::thread_rng().fill_bytes(&mut dek);
+        let wrapped_dek_b64 = self.encrypt_data(&dek, &group_key)?;
+        let wrapped_dek = general_purpose::STANDARD.decode(&wrapped_dek_b64).map_err(|_| NovaError::InvalidKey)?;
+        let header = Self::build_envelope(&wrapped_dek, &[]);
+
+        let mut iv = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut iv);
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<bytes::Bytes, std::io::Error>>(4);
+        tx.send(Ok(bytes::Bytes::from(header)))
+            .await
+            .map_err(|_| NovaError::Near("upload stream closed early".to_string()))?;
+        tx.send(Ok(bytes::Bytes::copy_from_slice(&iv)))
+            .await
+            .map_err(|_| NovaError::Near("upload stream closed early".to_string()))?;
+
+        let file_hash = tokio::spawn(async move {
+            let mut encryptor = ChunkEncryptor::new(&dek, &iv);
+            let mut buf = vec![0u8; CHUNK_SIZE];
+            loop {
+                let n = match reader.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return None;
+                    }
+                };
+                let ciphertext = encryptor.update(&buf[..n]);
+                if !ciphertext.is_empty() && tx.send(Ok(bytes::Bytes::from(ciphertext))).await.is_err() {
+                    return None;
+                }
+            }
+            match encryptor.finalize() {
+                Ok((final_block, hash)) => {
+                    if !final_block.is_empty() {
+                        let _ = tx.send(Ok(bytes::Bytes::from(final_block))).await;
+                    }
+                    Some(hash)
+                }
+                Err(_) => None,
+            }
+        });
+
+        let body = reqwest::Body::wrap_stream(tokio_stream::wrappers::ReceiverStream::new(rx));
+        let cid = self.ipfs_upload_body(body, filename).await?;
+
+        let hash_bytes = file_hash
+            .await
+            .map_err(|e| NovaError::Near(format!("streaming encrypt task failed: {}", e)))?
+            .ok_or_else(|| NovaError::Near("streaming encryption failed".to_string()))?;
+        let file_hash = hex_encode(&hash_bytes);
+
+        let trans_id = self.record_transaction(group_id, user_id, &file_hash, &cid, None, false).await?;
+
         Ok(CompositeUploadResult {
             cid,
             trans_id,
@@ -381,111 +1455,452 @@ impl NovaSdk {  // REMOVED generic type parameter
         })
     }
 
-    // Full retrieve workflow: get_key → fetch IPFS → decrypt.
-    pub async fn composite_retrieve(
+    // Full retrieve workflow: get_key → fetch IPFS → decrypt.
+    pub async fn composite_retrieve(
+        &self,
+        group_id: &str,
+        ipfs_hash: &str,
+    ) -> Result<CompositeRetrieveResult, NovaError> {
+        // Validate CID format
+        if !ipfs_hash.starts_with("Qm") {
+            return Err(NovaError::Near(format!("Invalid CID: {}", ipfs_hash)));
+        }
+        
+        // Step 1: Get user_id from signer
+        let user_id = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| NovaError::Signing("No signer attached for retrieve".to_string()))?
+            .account_id()
+            .to_string();
+        
+        // Step 2: Fetch the key version this file was actually sealed
+        // under, not just the group's current one — after a rotation the
+        // latest key can no longer open an older file.
+        let transactions = self.get_transactions_for_group(group_id, &user_id).await?;
+        let key_version = transactions
+            .iter()
+            .find(|tx| tx.ipfs_hash == ipfs_hash)
+            .map(|tx| tx.key_version)
+            .ok_or_else(|| NovaError::Validation(format!("no recorded transaction for {} in group {}", ipfs_hash, group_id)))?;
+        let key = self.get_group_key_at_version(group_id, &user_id, key_version).await?;
+
+        // Step 3: Fetch from IPFS
+        let encrypted_b64 = self.ipfs_retrieve(ipfs_hash).await?;
+
+        // Step 3b: Verify the gateway actually returned the content the CID
+        // names, independent of (and before) the file_hash check below —
+        // this is what catches a gateway substituting content when it also
+        // controls the recorded hash path.
+        let fetched_bytes = general_purpose::STANDARD
+            .decode(&encrypted_b64)
+            .map_err(|_| NovaError::InvalidKey)?;
+        cid::verify_cid(ipfs_hash, &fetched_bytes)?;
+
+        // Step 4: Unwrap the DEK and decrypt the body
+        let decrypted_b64 = self.envelope_decrypt(&encrypted_b64, &key)?;
+        
+        // Step 5: Calculate hash for verification
+        let decrypted_bytes = general_purpose::STANDARD.decode(&decrypted_b64)
+            .map_err(|_| NovaError::InvalidKey)?;
+        let file_hash = hex_encode(&sha256_hash(&decrypted_bytes));
+
+        Ok(CompositeRetrieveResult {
+            data: decrypted_bytes,
+            file_hash,
+        })
+    }
+
+    // Streaming counterpart of `composite_retrieve`: fetches the envelope
+    // from IPFS as it arrives, parses the `composite_upload_stream` header
+    // (`MAGIC || VERSION || wrapped_dek_len || wrapped_dek`) off the front
+    // of the stream, unwraps the DEK, and decrypts the remaining `IV ||
+    // ciphertext` body with `ChunkDecryptor` chunk by chunk, writing
+    // plaintext straight into `writer` instead of materializing the whole
+    // file.
+    //
+    // UNVERIFIED UNTIL THIS RETURNS `Ok`, unlike every other
+    // `composite_retrieve*`: the CID can only be checked once the stream
+    // is exhausted, by which point every byte of plaintext has already
+    // reached `writer` — a malicious gateway's substituted ciphertext gets
+    // decrypted and handed to the caller before the integrity check ever
+    // runs. The `_unverified` suffix is load-bearing, not decorative:
+    // don't act on anything read from `writer` (forward it, persist it,
+    // etc.) until this call returns `Ok`, and discard/roll back whatever
+    // was written if it returns `Err`. Callers that need the CID check to
+    // gate output before any of it becomes visible should buffer with
+    // `composite_retrieve` instead.
+    pub async fn composite_retrieve_stream_unverified(
+        &self,
+        group_id: &str,
+        ipfs_hash: &str,
+        mut writer: impl tokio::io::AsyncWrite + Unpin,
+    ) -> Result<String, NovaError> {
+        use sha2::{Digest, Sha256};
+        use tokio::io::AsyncWriteExt;
+        use tokio_stream::StreamExt;
+
+        if !ipfs_hash.starts_with("Qm") {
+            return Err(NovaError::Near(format!("Invalid CID: {}", ipfs_hash)));
+        }
+
+        let user_id = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| NovaError::Signing("No signer attached for retrieve".to_string()))?
+            .account_id()
+            .to_string();
+
+        let transactions = self.get_transactions_for_group(group_id, &user_id).await?;
+        let key_version = transactions
+            .iter()
+            .find(|tx| tx.ipfs_hash == ipfs_hash)
+            .map(|tx| tx.key_version)
+            .ok_or_else(|| NovaError::Validation(format!("no recorded transaction for {} in group {}", ipfs_hash, group_id)))?;
+        let group_key = self.get_group_key_at_version(group_id, &user_id, key_version).await?;
+
+        let url = format!("https://gateway.pinata.cloud/ipfs/{}", ipfs_hash);
+        let response = reqwest::get(&url).await.map_err(Self::classify_gateway_error)?;
+        let response = response.error_for_status().map_err(Self::classify_gateway_error)?;
+        let mut stream = response.bytes_stream();
+
+        let mut raw_hasher = Sha256::new();
+        let mut header_buf: Vec<u8> = Vec::with_capacity(7);
+        let mut wrapped_dek: Option<Vec<u8>> = None;
+        let mut iv_buf: Vec<u8> = Vec::with_capacity(16);
+        let mut decryptor: Option<ChunkDecryptor> = None;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(Self::classify_gateway_error)?;
+            raw_hasher.update(&chunk);
+            let mut chunk: &[u8] = &chunk;
+
+            if wrapped_dek.is_none() {
+                header_buf.extend_from_slice(chunk);
+                if header_buf.len() < 7 {
+                    continue;
+                }
+                if header_buf[..4] != ENVELOPE_MAGIC {
+                    return Err(NovaError::Near("Not a Nova envelope (bad magic)".to_string()));
+                }
+                if header_buf[4] != ENVELOPE_VERSION {
+                    return Err(NovaError::Near(format!("Unsupported envelope version: {}", header_buf[4])));
+                }
+                let wrapped_dek_len = u16::from_be_bytes([header_buf[5], header_buf[6]]) as usize;
+                let header_len = 7 + wrapped_dek_len;
+                if header_buf.len() < header_len {
+                    continue;
+                }
+                wrapped_dek = Some(header_buf[7..header_len].to_vec());
+                let leftover_in_buf = header_buf.len() - header_len;
+                chunk = &chunk[chunk.len() - leftover_in_buf..];
+            }
+
+            if decryptor.is_none() {
+                let take = (16 - iv_buf.len()).min(chunk.len());
+                iv_buf.extend_from_slice(&chunk[..take]);
+                chunk = &chunk[take..];
+                if iv_buf.len() < 16 {
+                    continue;
+                }
+                let wrapped_dek_b64 = general_purpose::STANDARD.encode(wrapped_dek.as_ref().unwrap());
+                let dek_b64 = self.decrypt_data(&wrapped_dek_b64, &group_key)?;
+                let dek_bytes = general_purpose::STANDARD.decode(&dek_b64).map_err(|_| NovaError::InvalidKey)?;
+                if dek_bytes.len() != 32 {
+                    return Err(NovaError::InvalidKey);
+                }
+                let mut dek = [0u8; 32];
+                dek.copy_from_slice(&dek_bytes);
+                let mut iv = [0u8; 16];
+                iv.copy_from_slice(&iv_buf);
+                decryptor = Some(ChunkDecryptor::new(&dek, &iv));
+            }
+
+            if !chunk.is_empty() {
+                let plaintext = decryptor.as_mut().unwrap().update(chunk)?;
+                if !plaintext.is_empty() {
+                    writer
+                        .write_all(&plaintext)
+                        .await
+                        .map_err(|e| NovaError::Near(format!("stream write failed: {}", e)))?;
+                }
+            }
+        }
+
+        let decryptor = decryptor.ok_or_else(|| NovaError::Near("Truncated envelope".to_string()))?;
+        let (final_plaintext, file_hash_bytes) = decryptor.finalize()?;
+        if !final_plaintext.is_empty() {
+            writer
+                .write_all(&final_plaintext)
+                .await
+                .map_err(|e| NovaError::Near(format!("stream write failed: {}", e)))?;
+        }
+        writer.flush().await.map_err(|e| NovaError::Near(format!("stream flush failed: {}", e)))?;
+
+        let raw_digest: [u8; 32] = raw_hasher.finalize().into();
+        cid::verify_digest(ipfs_hash, &raw_digest)?;
+
+        Ok(hex_encode(&file_hash_bytes))
+    }
+
+    // Redundant counterpart of `composite_upload`: encrypts the same way,
+    // then Reed-Solomon-splits the envelope into `k` data shards + `m`
+    // parity shards (see the `erasure` module), pins each shard to IPFS
+    // independently, and pins a `ShardManifest` pointing at all of them.
+    // The manifest's own CID — not a shard's — is what gets recorded
+    // on-chain via `record_transaction`, so `composite_retrieve_redundant`
+    // only needs that one CID to find every shard.
+    pub async fn composite_upload_redundant(
+        &self,
+        group_id: &str,
+        user_id: &str,
+        data: &[u8],
+        filename: &str,
+        k: usize,
+        m: usize,
+    ) -> Result<CompositeUploadRedundantResult, NovaError> {
+        if !self.is_authorized(group_id, user_id).await? {
+            return Err(NovaError::Validation(format!("{} is not authorized in group {}", user_id, group_id)));
+        }
+
+        let key = self.get_group_key(group_id, user_id).await?;
+        let encrypted_b64 = self.envelope_encrypt(data, &key)?;
+        let envelope = general_purpose::STANDARD.decode(&encrypted_b64).map_err(|_| NovaError::InvalidKey)?;
+
+        let shards = erasure::encode(&envelope, k, m)?;
+        let mut shard_cids = Vec::with_capacity(shards.len());
+        for (i, shard) in shards.iter().enumerate() {
+            let shard_b64 = general_purpose::STANDARD.encode(shard);
+            let shard_cid = self.ipfs_upload(&shard_b64, &format!("{}.shard{}", filename, i)).await?;
+            shard_cids.push(shard_cid);
+        }
+
+        let manifest = ShardManifest {
+            shard_cids: shard_cids.clone(),
+            k,
+            m,
+            original_len: envelope.len(),
+        };
+        let manifest_json = serde_json::to_vec(&manifest)
+            .map_err(|e| NovaError::Near(format!("manifest serialization failed: {}", e)))?;
+        let manifest_b64 = general_purpose::STANDARD.encode(&manifest_json);
+        let manifest_cid = self.ipfs_upload(&manifest_b64, &format!("{}.manifest.json", filename)).await?;
+
+        let file_hash = hex_encode(&sha256_hash(data));
+        let trans_id = self.record_transaction(group_id, user_id, &file_hash, &manifest_cid, None, false).await?;
+
+        Ok(CompositeUploadRedundantResult {
+            manifest_cid,
+            shard_cids,
+            trans_id,
+            file_hash,
+        })
+    }
+
+    // Redundant counterpart of `composite_retrieve`: `manifest_cid` names a
+    // `ShardManifest` instead of the file itself. Fetches as many of the
+    // `k + m` shards as it can — a fetch failure or a failed `verify_cid`
+    // just drops that shard rather than failing the whole retrieve — then
+    // lets `erasure::decode` reconstruct from whichever `k` (or more) came
+    // back before decrypting exactly as `composite_retrieve` does.
+    pub async fn composite_retrieve_redundant(
         &self,
         group_id: &str,
-        ipfs_hash: &str,
+        manifest_cid: &str,
     ) -> Result<CompositeRetrieveResult, NovaError> {
-        // Validate CID format
-        if !ipfs_hash.starts_with("Qm") {
-            return Err(NovaError::Near(format!("Invalid CID: {}", ipfs_hash)));
+        if !manifest_cid.starts_with("Qm") {
+            return Err(NovaError::Near(format!("Invalid CID: {}", manifest_cid)));
         }
-        
-        // Step 1: Get user_id from signer
-        let user_id = match &self.signer {
-            Some(Signer::InMemory(s)) => s.account_id.to_string(),
-            None => return Err(NovaError::Signing("No signer attached for retrieve".to_string())),
-            _ => return Err(NovaError::Signing("Unsupported signer type".to_string())),
-        };
-        
-        // Step 2: Fetch group key
-        let key_b64 = self.get_group_key(group_id, &user_id).await?;
-        
-        // Step 3: Fetch from IPFS
-        let encrypted_b64 = self.ipfs_retrieve(ipfs_hash).await?;
-        
-        // Step 4: Decrypt
-        let decrypted_b64 = self.decrypt_data(&encrypted_b64, &key_b64)?;
-        
-        // Step 5: Calculate hash for verification
-        let decrypted_bytes = general_purpose::STANDARD.decode(&decrypted_b64)
-            .map_err(|_| NovaError::InvalidKey)?;
+
+        let user_id = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| NovaError::Signing("No signer attached for retrieve".to_string()))?
+            .account_id()
+            .to_string();
+
+        let transactions = self.get_transactions_for_group(group_id, &user_id).await?;
+        let key_version = transactions
+            .iter()
+            .find(|tx| tx.ipfs_hash == manifest_cid)
+            .map(|tx| tx.key_version)
+            .ok_or_else(|| NovaError::Validation(format!("no recorded transaction for {} in group {}", manifest_cid, group_id)))?;
+        let key = self.get_group_key_at_version(group_id, &user_id, key_version).await?;
+
+        let manifest_b64 = self.ipfs_retrieve(manifest_cid).await?;
+        let manifest_bytes = general_purpose::STANDARD.decode(&manifest_b64).map_err(|_| NovaError::InvalidKey)?;
+        cid::verify_cid(manifest_cid, &manifest_bytes)?;
+        let manifest: ShardManifest = serde_json::from_slice(&manifest_bytes)
+            .map_err(|e| NovaError::Near(format!("manifest parse failed: {}", e)))?;
+
+        let mut shards: Vec<Option<Vec<u8>>> = Vec::with_capacity(manifest.shard_cids.len());
+        for shard_cid in &manifest.shard_cids {
+            let shard = match self.ipfs_retrieve(shard_cid).await {
+                Ok(shard_b64) => general_purpose::STANDARD
+                    .decode(&shard_b64)
+                    .ok()
+                    .filter(|bytes| cid::verify_cid(shard_cid, bytes).is_ok()),
+                Err(_) => None,
+            };
+            shards.push(shard);
+        }
+
+        let envelope = erasure::decode(shards, manifest.k, manifest.m, manifest.original_len)?;
+        let envelope_b64 = general_purpose::STANDARD.encode(&envelope);
+        let decrypted_b64 = self.envelope_decrypt(&envelope_b64, &key)?;
+        let decrypted_bytes = general_purpose::STANDARD.decode(&decrypted_b64).map_err(|_| NovaError::InvalidKey)?;
         let file_hash = hex_encode(&sha256_hash(&decrypted_bytes));
-        
+
         Ok(CompositeRetrieveResult {
             data: decrypted_bytes,
             file_hash,
         })
     }
 
-    // Helper: Encrypt data with AES-256-CBC
-    fn encrypt_data(&self, data: &[u8], key_b64: &str) -> Result<String, NovaError> {
+    // Helper: Encrypt data with AES-256-CBC. `key` must already be a
+    // validated 32-byte `Secret` (see `get_group_key`).
+    fn encrypt_data(&self, data: &[u8], key: &Secret) -> Result<String, NovaError> {
         use aes::Aes256;
         use cbc::cipher::{block_padding::Pkcs7, BlockEncryptMut, KeyIvInit};
-        
+
         type Aes256CbcEnc = cbc::Encryptor<Aes256>;
-        
-        // Decode key
-        let key_bytes = general_purpose::STANDARD.decode(key_b64)
-            .map_err(|_| NovaError::InvalidKey)?;
+
+        let key_bytes = key.expose_bytes();
         if key_bytes.len() != 32 {
             return Err(NovaError::InvalidKey);
         }
-        
+
         // Generate random IV (16 bytes)
         let mut iv = [0u8; 16];
         use rand::RngCore;
         rand::thread_rng().fill_bytes(&mut iv);
-        
+
         // Prepare buffer with room for padding
         let mut buffer = vec![0u8; data.len() + 16];
         buffer[..data.len()].copy_from_slice(data);
 
         // Encrypt with padding
-        let cipher = Aes256CbcEnc::new(key_bytes.as_slice().into(), &iv.into());
+        let cipher = Aes256CbcEnc::new(key_bytes.into(), &iv.into());
         let ciphertext = cipher.encrypt_padded_mut::<Pkcs7>(&mut buffer, data.len())
             .map_err(|_| NovaError::Near("Encryption failed".to_string()))?;
-        
+
         // Prepend IV to ciphertext
         let mut result = iv.to_vec();
         result.extend_from_slice(ciphertext);
-        
+
         Ok(general_purpose::STANDARD.encode(result))
     }
 
-    // Helper: Decrypt data with AES-256-CBC
-    fn decrypt_data(&self, encrypted_b64: &str, key_b64: &str) -> Result<String, NovaError> {
+    // Helper: Decrypt data with AES-256-CBC. `key` must already be a
+    // validated 32-byte `Secret` (see `get_group_key`).
+    fn decrypt_data(&self, encrypted_b64: &str, key: &Secret) -> Result<String, NovaError> {
         use aes::Aes256;
         use cbc::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
-        
+
         type Aes256CbcDec = cbc::Decryptor<Aes256>;
-        
-        // Decode key and encrypted data
-        let key_bytes = general_purpose::STANDARD.decode(key_b64)
-            .map_err(|_| NovaError::InvalidKey)?;
+
+        let key_bytes = key.expose_bytes();
         if key_bytes.len() != 32 {
             return Err(NovaError::InvalidKey);
         }
-        
+
         let encrypted_bytes = general_purpose::STANDARD.decode(encrypted_b64)
             .map_err(|_| NovaError::InvalidKey)?;
         if encrypted_bytes.len() < 16 {
             return Err(NovaError::InvalidKey);
         }
-        
+
         // Extract IV (first 16 bytes) and ciphertext
         let (iv, ciphertext) = encrypted_bytes.split_at(16);
-        
+
         // Decrypt with padding removal
-        let cipher = Aes256CbcDec::new(key_bytes.as_slice().into(), iv.into());
+        let cipher = Aes256CbcDec::new(key_bytes.into(), iv.into());
         let mut buffer = ciphertext.to_vec();
         let decrypted = cipher.decrypt_padded_mut::<Pkcs7>(&mut buffer)
             .map_err(|_| NovaError::Near("Decryption failed".to_string()))?;
-        
+
         Ok(general_purpose::STANDARD.encode(decrypted))
     }
 
+    // Envelope-encrypts `data`: a fresh random 32-byte DEK encrypts the
+    // body, and the DEK itself is wrapped (a second AES-256-CBC pass)
+    // under `group_key`. The result is `MAGIC || VERSION || wrapped_dek_len
+    // (u16 BE) || wrapped_dek || body`, so the format is self-describing
+    // and a later group-key rotation (`rotate_group_key`) only needs to
+    // re-wrap the DEK rather than re-encrypt every file body.
+    fn envelope_encrypt(&self, data: &[u8], group_key: &Secret) -> Result<String, NovaError> {
+        let mut dek_bytes = [0u8; 32];
+        use rand::RngCore;
+        rand::thread_rng().fill_bytes(&mut dek_bytes);
+        let dek = Secret::new(dek_bytes.to_vec());
+
+        let body_b64 = self.encrypt_data(data, &dek)?;
+        let body = general_purpose::STANDARD.decode(&body_b64).map_err(|_| NovaError::InvalidKey)?;
+
+        let wrapped_dek_b64 = self.encrypt_data(&dek_bytes, group_key)?;
+        let wrapped_dek = general_purpose::STANDARD.decode(&wrapped_dek_b64).map_err(|_| NovaError::InvalidKey)?;
+
+        Ok(general_purpose::STANDARD.encode(Self::build_envelope(&wrapped_dek, &body)))
+    }
+
+    // Reverses `envelope_encrypt`: splits the header to recover the
+    // wrapped DEK and body, unwraps the DEK under `group_key`, then
+    // decrypts the body under the recovered DEK.
+    fn envelope_decrypt(&self, envelope_b64: &str, group_key: &Secret) -> Result<String, NovaError> {
+        let envelope = general_purpose::STANDARD.decode(envelope_b64).map_err(|_| NovaError::InvalidKey)?;
+        let (wrapped_dek, body) = Self::split_envelope(&envelope)?;
+
+        let wrapped_dek_b64 = general_purpose::STANDARD.encode(wrapped_dek);
+        let dek_b64 = self.decrypt_data(&wrapped_dek_b64, group_key)?;
+        let dek_bytes = general_purpose::STANDARD.decode(&dek_b64).map_err(|_| NovaError::InvalidKey)?;
+        let dek = Secret::new(dek_bytes);
+
+        let body_b64 = general_purpose::STANDARD.encode(body);
+        self.decrypt_data(&body_b64, &dek)
+    }
+
+    // Swaps the wrapped-DEK header of an envelope from `old_key` to
+    // `new_key`, leaving the encrypted body untouched. Used by
+    // `rotate_group_key`.
+    fn rewrap_envelope(&self, envelope_b64: &str, old_key: &Secret, new_key: &Secret) -> Result<String, NovaError> {
+        let envelope = general_purpose::STANDARD.decode(envelope_b64).map_err(|_| NovaError::InvalidKey)?;
+        let (wrapped_dek, body) = Self::split_envelope(&envelope)?;
+
+        let wrapped_dek_b64 = general_purpose::STANDARD.encode(wrapped_dek);
+        let dek_b64 = self.decrypt_data(&wrapped_dek_b64, old_key)?;
+        let dek_bytes = general_purpose::STANDARD.decode(&dek_b64).map_err(|_| NovaError::InvalidKey)?;
+
+        let new_wrapped_dek_b64 = self.encrypt_data(&dek_bytes, new_key)?;
+        let new_wrapped_dek = general_purpose::STANDARD.decode(&new_wrapped_dek_b64).map_err(|_| NovaError::InvalidKey)?;
+
+        Ok(general_purpose::STANDARD.encode(Self::build_envelope(&new_wrapped_dek, body)))
+    }
+
+    fn build_envelope(wrapped_dek: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut envelope = Vec::with_capacity(4 + 1 + 2 + wrapped_dek.len() + body.len());
+        envelope.extend_from_slice(&ENVELOPE_MAGIC);
+        envelope.push(ENVELOPE_VERSION);
+        envelope.extend_from_slice(&(wrapped_dek.len() as u16).to_be_bytes());
+        envelope.extend_from_slice(wrapped_dek);
+        envelope.extend_from_slice(body);
+        envelope
+    }
+
+    fn split_envelope(envelope: &[u8]) -> Result<(&[u8], &[u8]), NovaError> {
+        if envelope.len() < 7 || envelope[..4] != ENVELOPE_MAGIC {
+            return Err(NovaError::Near("Not a Nova envelope (bad magic)".to_string()));
+        }
+        if envelope[4] != ENVELOPE_VERSION {
+            return Err(NovaError::Near(format!("Unsupported envelope version: {}", envelope[4])));
+        }
+        let wrapped_dek_len = u16::from_be_bytes([envelope[5], envelope[6]]) as usize;
+        let header_len = 7 + wrapped_dek_len;
+        if envelope.len() < header_len {
+            return Err(NovaError::Near("Truncated envelope".to_string()));
+        }
+        Ok((&envelope[7..header_len], &envelope[header_len..]))
+    }
+
     // Helper: Upload to IPFS via Pinata
     async fn ipfs_upload(&self, data_b64: &str, filename: &str) -> Result<String, NovaError> {
         use reqwest::multipart;
@@ -516,15 +1931,63 @@ impl NovaSdk {  // REMOVED generic type parameter
             .ok_or(NovaError::Near("No IpfsHash in response".to_string()))
     }
 
+    // Helper: streams a pre-built body straight into Pinata's pinning
+    // endpoint, for callers (like `composite_upload_stream`) that produce
+    // ciphertext incrementally instead of holding it all in one buffer.
+    async fn ipfs_upload_body(&self, body: reqwest::Body, filename: &str) -> Result<String, NovaError> {
+        use reqwest::multipart;
+
+        let client = reqwest::Client::new();
+        let part = multipart::Part::stream(body).file_name(filename.to_string());
+        let form = multipart::Form::new().part("file", part);
+
+        let response = client
+            .post("https://api.pinata.cloud/pinning/pinFileToIPFS")
+            .header("pinata_api_key", &self.pinata_key)
+            .header("pinata_secret_api_key", &self.pinata_secret)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| NovaError::Near(format!("IPFS upload failed: {}", e)))?;
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| NovaError::Near(format!("IPFS response parse failed: {}", e)))?;
+
+        json["IpfsHash"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or(NovaError::Near("No IpfsHash in response".to_string()))
+    }
+
+    // Classifies a `reqwest::Error`/HTTP status into something
+    // `NovaError::is_retryable` can act on, instead of flattening
+    // everything into `NovaError::Near` (which `is_retryable` never
+    // matches) the way `_inner_retrieve` used to.
+    fn classify_gateway_error(e: reqwest::Error) -> NovaError {
+        if e.is_timeout() {
+            NovaError::Timeout
+        } else if e.status() == Some(reqwest::StatusCode::TOO_MANY_REQUESTS) {
+            NovaError::RateLimited
+        } else {
+            NovaError::Near(format!("IPFS retrieve failed: {}", e))
+        }
+    }
+
     // Helper: Retrieve from IPFS via Pinata gateway
     async fn _inner_retrieve(&self, cid: &str, client: &reqwest::Client) -> Result<String, NovaError> {
         let url = format!("https://gateway.pinata.cloud/ipfs/{}", cid);
         let response = client.get(&url)
             .send()
             .await
-            .map_err(|e| NovaError::Near(format!("IPFS retrieve failed: {}", e)))?;
+            .map_err(Self::classify_gateway_error)?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(NovaError::RateLimited);
+        }
+        let response = response.error_for_status().map_err(Self::classify_gateway_error)?;
         let bytes = response.bytes().await
-            .map_err(|e| NovaError::Near(format!("IPFS read failed: {}", e)))?;
+            .map_err(Self::classify_gateway_error)?;
         Ok(general_purpose::STANDARD.encode(bytes))
     }
 
@@ -534,7 +1997,7 @@ impl NovaSdk {  // REMOVED generic type parameter
         while retries < 3 {
             match self._inner_retrieve(cid, &client).await {
                 Ok(res) => return Ok(res),
-                Err(e) if e.to_string().contains("timeout") => {
+                Err(e) if e.is_retryable() => {
                     retries += 1;
                     sleep(Duration::from_secs(2u64.pow((retries as u64).try_into().unwrap()))).await;
                 }
@@ -555,7 +2018,7 @@ impl NovaSdk {  // REMOVED generic type parameter
 }
 
 // Helper function for SHA-256 hashing
-fn sha256_hash(data: &[u8]) -> [u8; 32] {
+pub(crate) fn sha256_hash(data: &[u8]) -> [u8; 32] {
     use sha2::{Sha256, Digest};
     let mut hasher = Sha256::new();
     hasher.update(data);
@@ -582,6 +2045,19 @@ mod tests {
         );
         assert_eq!(sdk.contract_id.as_str(), "nova-sdk-2.testnet");
         assert!(sdk.signer.is_none());
+        assert!(matches!(sdk.read_finality, BlockReference::Finality(Finality::Final)));
+    }
+
+    #[tokio::test]
+    async fn test_with_finality_optimistic() {
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        )
+        .with_finality(BlockReference::Finality(Finality::Optimistic));
+        assert!(matches!(sdk.read_finality, BlockReference::Finality(Finality::Optimistic)));
     }
 
     #[tokio::test]
@@ -604,6 +2080,18 @@ mod tests {
         assert!(matches!(result.err().unwrap(), NovaError::ParseAccount));
     }
 
+    #[tokio::test]
+    async fn test_with_signer_secp256k1_dispatch() {
+        // Dummy secp256k1-prefixed key: still expects a Signing error on the
+        // bad base58, but proves `with_signer` routes to the secp256k1 path
+        // instead of assuming ed25519 from the prefix alone.
+        let private_key = "secp256k1:dummy";
+        let account_id = "test.account.testnet";
+        let result = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
+            .with_signer(private_key, account_id);
+        assert!(matches!(result.err().unwrap(), NovaError::Signing(_)));
+    }
+
     #[tokio::test]
     async fn test_get_balance() {
         let sdk = NovaSdk::new(
@@ -647,20 +2135,18 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_group_key_authorized() {
-        // Skip unless TEST_NEAR_ACCOUNT_ID set (like integration.rs)
+        // Skip unless both env vars are set (like integration.rs): unwrapping
+        // the caller's wrapped entry needs a signer, not just an account id.
+        let private_key = std::env::var("TEST_NEAR_PRIVATE_KEY").ok();
         let account_id = std::env::var("TEST_NEAR_ACCOUNT_ID").ok();
-        if account_id.is_none() {
+        if private_key.is_none() || account_id.is_none() {
             return;
         }
-        let sdk = NovaSdk::new(
-            "https://rpc.testnet.near.org",
-            "nova-sdk-2.testnet",
-            "fake_key",
-            "fake_secret",
-        );
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake_key", "fake_secret")
+            .with_signer(&private_key.unwrap(), &account_id.clone().unwrap())
+            .unwrap();
         let key = sdk.get_group_key("test_group", &account_id.unwrap()).await.unwrap();
-        assert!(!key.is_empty(), "Key should be non-empty base64");
-        assert!(key.len() > 20, "Base64 key should be reasonable length");
+        assert_eq!(key.expose_bytes().len(), 32, "Key should decode to a 32-byte AES-256 key");
     }
 
     #[tokio::test]
@@ -715,6 +2201,31 @@ mod tests {
     assert!(matches!(result.err().unwrap(), NovaError::Near(_)));
     }
 
+    #[tokio::test]
+    async fn test_sign_only_no_signer() {
+        // With an explicit nonce/block_hash, sign_only never touches the
+        // network, so the only way it can fail here is the missing signer.
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        );
+        let result = sdk
+            .sign_only(
+                "test.account.testnet",
+                "ed25519:1thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE",
+                "record_transaction",
+                b"{}".to_vec(),
+                None,
+                0,
+                1,
+                CryptoHash::default(),
+            )
+            .await;
+        assert!(matches!(result.err().unwrap(), NovaError::Signing(_)));
+    }
+
     #[tokio::test]
     #[should_panic(expected = "No signer attached")]
     async fn test_register_group_no_signer() {
@@ -754,10 +2265,12 @@ mod tests {
         }
         let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
             .with_signer(&private_key.unwrap(), &account_id.unwrap()).unwrap();
-        let result = sdk.add_group_member("test_group", "new.member.testnet").await;
+        let result = sdk.add_group_member("test_group", "new.member.testnet", false).await;
         match result {
             Ok(_) => println!("✅ Added member successfully"),
-            Err(e) => if e.to_string().contains("already a member") { println!("Already member - expected") } else { panic!("Unexpected error: {}", e) },
+            // The contract now fails with the stable `ALREADY_MEMBER` code
+            // (see `contract::error::NovaError`) instead of free text.
+            Err(e) => if e.to_string().contains("ALREADY_MEMBER") { println!("Already member - expected") } else { panic!("Unexpected error: {}", e) },
         }
     }
 
@@ -770,7 +2283,7 @@ mod tests {
             "fake_key",
             "fake_secret",
         );
-        let _ = sdk.revoke_group_member("test_group", "test.user.testnet").await.unwrap();
+        let _ = sdk.revoke_group_member("test_group", "test.user.testnet", false).await.unwrap();
     }
 
     #[tokio::test]
@@ -784,7 +2297,7 @@ mod tests {
         let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
             .with_signer(&private_key.unwrap(), &account_id.unwrap()).unwrap();
         // Revoke non-member → expect contract error (user not found)
-        let result = sdk.revoke_group_member("test_group", "non.member.testnet").await;
+        let result = sdk.revoke_group_member("test_group", "non.member.testnet", false).await;
         assert!(result.is_err(), "Revoking non-member should fail");
         assert!(matches!(result.err().unwrap(), NovaError::Near(_)));
     }
@@ -798,24 +2311,18 @@ mod tests {
             "fake_key",
             "fake_secret",
         );
-        let _ = sdk.store_group_key("test_group", "dummyb64key").await.unwrap();
+        let wrapped_keys = vec![("member.testnet".to_string(), "dummy-wrapped-key".to_string())];
+        let _ = sdk.store_group_key("test_group", &wrapped_keys, false).await.unwrap();
     }
 
     #[tokio::test]
     async fn test_store_group_key_invalid_key() {
-        let private_key = std::env::var("TEST_NEAR_PRIVATE_KEY").ok();
-        let account_id = std::env::var("TEST_NEAR_ACCOUNT_ID").ok();
-        if private_key.is_none() || account_id.is_none() {
-            println!("Skipping test_store_group_key_invalid_key: Credentials not set");
-            return;
-        }
-        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
-            .with_signer(&private_key.unwrap(), &account_id.unwrap()).unwrap();
-        // Invalid base64 key → expect contract panic on decode/len
+        // Invalid base64/length is now rejected locally by `Secret::from_base64`
+        // instead of round-tripping to the contract.
         let invalid_key = "invalid_not_base64";
-        let result = sdk.store_group_key("test_group", invalid_key).await;
-        assert!(result.is_err(), "Invalid key should fail");
-        assert!(matches!(result.err().unwrap(), NovaError::Near(_)));
+        let result = Secret::from_base64(invalid_key, 32);
+        assert!(result.is_err(), "Invalid key should fail construction");
+        assert!(matches!(result.err().unwrap(), NovaError::InvalidKey));
     }
 
     #[tokio::test]
@@ -827,7 +2334,21 @@ mod tests {
             "fake_key",
             "fake_secret",
         );
-        let _ = sdk.record_transaction("test_group", "user.testnet", "dummy_hash", "QmDummyCID").await.unwrap();
+        let _ = sdk.record_transaction("test_group", "user.testnet", "dummy_hash", "QmDummyCID", None, false).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "No signer attached")]
+    async fn test_record_transaction_as_agent_no_signer() {
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        );
+        let dummy_hash = "a".repeat(64);
+        let dummy_cid = "QmYwAPJzv5CZsnAzt8auVZRnSW6aUezbbEMzyuUaVEF93A";
+        let _ = sdk.record_transaction_as_agent("test_group", "user.testnet", &dummy_hash, dummy_cid, 1).await.unwrap();
     }
 
     #[tokio::test]
@@ -840,10 +2361,14 @@ mod tests {
         }
         let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
             .with_signer(&private_key.unwrap(), &account_id.unwrap()).unwrap();
-        // Record for unauthorized user → expect contract error
-        let result = sdk.record_transaction("test_group", "unauth.user.testnet", "dummy_hash", "QmDummyCID").await;
+        // Record for unauthorized user → now caught locally by the
+        // pre-flight `is_authorized` check instead of round-tripping to a
+        // contract panic.
+        let dummy_hash = "a".repeat(64);
+        let dummy_cid = "QmYwAPJzv5CZsnAzt8auVZRnSW6aUezbbEMzyuUaVEF93A";
+        let result = sdk.record_transaction("test_group", "unauth.user.testnet", &dummy_hash, dummy_cid, None, false).await;
         assert!(result.is_err(), "Unauthorized user should fail");
-        assert!(matches!(result.err().unwrap(), NovaError::Near(_)));
+        assert!(matches!(result.err().unwrap(), NovaError::Validation(_)));
     }
 
 
@@ -856,7 +2381,7 @@ mod tests {
             "fake_key",
             "fake_secret",
         );
-        let _ = sdk.transfer_tokens("receiver.testnet", 1_000_000_000_000_000_000).await.unwrap(); // 1 NEAR
+        let _ = sdk.transfer_tokens("receiver.testnet", 1_000_000_000_000_000_000, false).await.unwrap(); // 1 NEAR
     }
 
     #[tokio::test]
@@ -869,10 +2394,76 @@ mod tests {
         }
         let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
             .with_signer(&private_key.unwrap(), &account_id.unwrap()).unwrap();
-        let result = sdk.transfer_tokens("invalid@to", 1);
+        let result = sdk.transfer_tokens("invalid@to", 1, false);
         assert!(matches!(result.await.err().unwrap(), NovaError::ParseAccount));
     }
 
+    #[tokio::test]
+    #[should_panic(expected = "No signer attached")]
+    async fn test_add_function_call_key_no_signer() {
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        );
+        let public_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519).public_key();
+        let _ = sdk
+            .add_function_call_key(
+                "member.testnet",
+                public_key,
+                None,
+                "nova-sdk-2.testnet",
+                vec!["record_transaction".to_string()],
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_function_call_key_rejects_foreign_account() {
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake_key", "fake_secret")
+            .with_signer("ed25519:1thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE", "member-a.testnet")
+            .unwrap();
+        let public_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519).public_key();
+        let result = sdk
+            .add_function_call_key(
+                "member-b.testnet",
+                public_key,
+                None,
+                "nova-sdk-2.testnet",
+                vec!["record_transaction".to_string()],
+            )
+            .await;
+        assert!(matches!(result, Err(NovaError::Validation(_))));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "No signer attached")]
+    async fn test_provision_group_member_key_no_signer() {
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        );
+        let public_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519).public_key();
+        let _ = sdk.provision_group_member_key(public_key, None).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "No signer attached")]
+    async fn test_delete_key_no_signer() {
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        );
+        let public_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519).public_key();
+        let _ = sdk.delete_key(public_key).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_encrypt_decrypt_binary() {
         let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake");
@@ -880,17 +2471,130 @@ mod tests {
         // Generate test key
         let mut key_bytes = [0u8; 32];
         rand::thread_rng().fill_bytes(&mut key_bytes);
-        let key_b64 = general_purpose::STANDARD.encode(key_bytes);
-    
+        let key = Secret::new(key_bytes.to_vec());
+
         // Test with binary data (not valid UTF-8)
         let original_data = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10]; // JPEG header
-        let encrypted = sdk.encrypt_data(&original_data, &key_b64).unwrap();
-        let decrypted_b64 = sdk.decrypt_data(&encrypted, &key_b64).unwrap();
+        let encrypted = sdk.encrypt_data(&original_data, &key).unwrap();
+        let decrypted_b64 = sdk.decrypt_data(&encrypted, &key).unwrap();
         let decrypted_bytes = general_purpose::STANDARD.decode(decrypted_b64).unwrap();
     
         assert_eq!(original_data, decrypted_bytes);
     }
 
+    #[tokio::test]
+    async fn test_envelope_encrypt_decrypt_roundtrip() {
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake");
+
+        let mut group_key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut group_key_bytes);
+        let group_key = Secret::new(group_key_bytes.to_vec());
+
+        let original_data = b"hello envelope encryption".to_vec();
+        let envelope = sdk.envelope_encrypt(&original_data, &group_key).unwrap();
+        let decrypted_b64 = sdk.envelope_decrypt(&envelope, &group_key).unwrap();
+        let decrypted_bytes = general_purpose::STANDARD.decode(decrypted_b64).unwrap();
+
+        assert_eq!(original_data, decrypted_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_envelope_decrypt_wrong_key_fails() {
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake");
+
+        let mut group_key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut group_key_bytes);
+        let group_key = Secret::new(group_key_bytes.to_vec());
+
+        let mut other_key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut other_key_bytes);
+        let other_key = Secret::new(other_key_bytes.to_vec());
+
+        let envelope = sdk.envelope_encrypt(b"secret payload", &group_key).unwrap();
+        assert!(sdk.envelope_decrypt(&envelope, &other_key).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rewrap_envelope_rotates_dek_wrapping() {
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake");
+
+        let mut old_key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut old_key_bytes);
+        let old_key = Secret::new(old_key_bytes.to_vec());
+
+        let mut new_key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut new_key_bytes);
+        let new_key = Secret::new(new_key_bytes.to_vec());
+
+        let original_data = b"data that survives rotation".to_vec();
+        let envelope = sdk.envelope_encrypt(&original_data, &old_key).unwrap();
+        let rewrapped = sdk.rewrap_envelope(&envelope, &old_key, &new_key).unwrap();
+
+        assert!(sdk.envelope_decrypt(&rewrapped, &old_key).is_err());
+        let decrypted_b64 = sdk.envelope_decrypt(&rewrapped, &new_key).unwrap();
+        let decrypted_bytes = general_purpose::STANDARD.decode(decrypted_b64).unwrap();
+        assert_eq!(original_data, decrypted_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_group_key_wrap_unwrap_roundtrip() {
+        let private_key = "ed25519:1thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE";
+        let account_id = "member.testnet";
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
+            .with_signer(private_key, account_id)
+            .unwrap();
+        let member_public_key = sdk.signer.as_ref().unwrap().public_key();
+
+        let data_key = group_key::generate_data_key();
+        let wrapped = group_key::wrap_for_member(&data_key, &member_public_key).unwrap();
+        let unwrapped = group_key::unwrap_for_member(&wrapped, sdk.signer.as_ref().unwrap().as_ref()).unwrap();
+
+        assert_eq!(unwrapped.expose_bytes(), &data_key);
+    }
+
+    #[tokio::test]
+    async fn test_group_key_unwrap_fails_for_wrong_member() {
+        let sdk_a = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
+            .with_signer("ed25519:1thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE", "member-a.testnet")
+            .unwrap();
+        let sdk_b = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake")
+            .with_signer("ed25519:58qpWRjVtJu1wvxjjdyD3GWQRfLbyd3wkCncDuUTFGvR", "member-b.testnet")
+            .unwrap();
+
+        let data_key = group_key::generate_data_key();
+        let wrapped = group_key::wrap_for_member(&data_key, &sdk_a.signer.as_ref().unwrap().public_key()).unwrap();
+
+        assert!(group_key::unwrap_for_member(&wrapped, sdk_b.signer.as_ref().unwrap().as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_group_key_roundtrip() {
+        let data_key = group_key::generate_data_key();
+        let commitment = hex_encode(&sha256_hash(&data_key));
+        let shares = shamir::split_secret(&data_key, 2, &[1, 2, 3]).unwrap();
+        let subset = vec![
+            (1u8, Secret::new(shares[0].to_vec())),
+            (2u8, Secret::new(shares[1].to_vec())),
+        ];
+
+        let reconstructed = NovaSdk::reconstruct_group_key(&subset, &commitment).unwrap();
+        assert_eq!(reconstructed.expose_bytes(), &data_key);
+    }
+
+    #[test]
+    fn test_reconstruct_group_key_rejects_commitment_mismatch() {
+        let data_key = group_key::generate_data_key();
+        let other_commitment = hex_encode(&sha256_hash(&group_key::generate_data_key()));
+        let shares = shamir::split_secret(&data_key, 2, &[1, 2, 3]).unwrap();
+        let subset = vec![
+            (1u8, Secret::new(shares[0].to_vec())),
+            (2u8, Secret::new(shares[1].to_vec())),
+        ];
+
+        let result = NovaSdk::reconstruct_group_key(&subset, &other_commitment);
+        assert!(matches!(result, Err(NovaError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn test_composite_upload_binary_integration() {
         let private_key = std::env::var("TEST_NEAR_PRIVATE_KEY").ok();
@@ -942,4 +2646,41 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), NovaError::Signing(_)));
     }
+
+    #[tokio::test]
+    async fn test_verify_transactions() {
+        let sdk = NovaSdk::new(
+            "https://rpc.testnet.near.org",
+            "nova-sdk-2.testnet",
+            "fake_key",
+            "fake_secret",
+        );
+        // Likely empty/unauthorized group → an empty chain trivially agrees
+        // with a zero head, or the call fails the same way
+        // `get_transactions_for_group` would.
+        let result = sdk.verify_transactions("test_group", "random.user.testnet").await;
+        match result {
+            Ok(first_break) => assert_eq!(first_break, None, "empty chain should have no break"),
+            Err(e) => assert!(matches!(e, NovaError::Near(_)), "Expect Near err for auth fail"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_composite_upload_redundant_no_signer() {
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake");
+        let test_data = b"test data";
+        let result = sdk
+            .composite_upload_redundant("test_group", "user.testnet", test_data, "test.txt", DEFAULT_DATA_SHARDS, DEFAULT_PARITY_SHARDS)
+            .await;
+        // Should fail at get_group_key or record_transaction (no signer)
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_composite_retrieve_redundant_no_signer() {
+        let sdk = NovaSdk::new("https://rpc.testnet.near.org", "nova-sdk-2.testnet", "fake", "fake");
+        let result = sdk.composite_retrieve_redundant("test_group", "QmDummyCID").await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), NovaError::Signing(_)));
+    }
 }
\ No newline at end of file
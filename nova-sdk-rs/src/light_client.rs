@@ -0,0 +1,291 @@
+//! Trustless verification of RPC responses via NEAR's light-client protocol.
+//!
+//! Instead of trusting a single RPC gateway's word for view-call results,
+//! `LightClient` keeps a locally-verified chain of block headers (bootstrapped
+//! from a trusted hash) and checks that any value returned by
+//! `EXPERIMENTAL_light_client_proof` Merkle-proves up to a header this struct
+//! has already validated.
+
+use std::collections::HashMap;
+
+use near_jsonrpc_client::{methods, JsonRpcClient};
+use near_jsonrpc_primitives::types::light_client::RpcLightClientExecutionProofRequest;
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::{AccountId, BlockHeight};
+use near_primitives::views::{LightClientBlockLiteView, LightClientBlockView, ValidatorStakeView};
+
+use crate::NovaError;
+
+/// Locally-tracked light-client head: the validator set (with stakes)
+/// that approved it, plus the block header they were approved against.
+#[derive(Debug, Clone)]
+pub struct LightClient {
+    head: LightClientBlockLiteView,
+    head_height: BlockHeight,
+    current_validators: Vec<ValidatorStakeView>,
+}
+
+impl LightClient {
+    /// Bootstraps a light client from a block the caller already trusts
+    /// (e.g. a hash pinned in config, or checkpointed out-of-band).
+    pub async fn bootstrap(
+        client: &JsonRpcClient,
+        trusted_block_hash: CryptoHash,
+    ) -> Result<Self, NovaError> {
+        let request = methods::next_light_client_block::RpcLightClientNextBlockRequest {
+            last_block_hash: trusted_block_hash,
+        };
+        let response = client
+            .call(request)
+            .await
+            .map_err(|e| NovaError::Near(e.to_string()))?;
+        let block = response
+            .ok_or_else(|| NovaError::ProofInvalid("no light client block available".into()))?;
+
+        let current_validators = block.next_bps.clone().unwrap_or_default();
+        Ok(LightClient {
+            head_height: block.inner_lite.height,
+            head: block.into(),
+            current_validators,
+        })
+    }
+
+    pub fn head_height(&self) -> BlockHeight {
+        self.head_height
+    }
+
+    pub fn head_hash(&self) -> CryptoHash {
+        self.head.hash()
+    }
+
+    /// Fetches the next light-client block and, if >= 2/3 of the current
+    /// validator set's stake signed it, adopts it as the new head.
+    pub async fn advance(&mut self, client: &JsonRpcClient) -> Result<bool, NovaError> {
+        let request = methods::next_light_client_block::RpcLightClientNextBlockRequest {
+            last_block_hash: self.head_hash(),
+        };
+        let response = client
+            .call(request)
+            .await
+            .map_err(|e| NovaError::Near(e.to_string()))?;
+        let Some(block) = response else {
+            return Ok(false);
+        };
+
+        self.verify_and_adopt(block)
+    }
+
+    fn verify_and_adopt(&mut self, block: LightClientBlockView) -> Result<bool, NovaError> {
+        let total_stake: u128 = self.current_validators.iter().map(stake_of).sum();
+        let approved_stake: u128 = block
+            .approvals_after_next
+            .iter()
+            .zip(self.current_validators.iter())
+            .filter_map(|(approval, validator)| approval.as_ref().map(|_| stake_of(validator)))
+            .sum();
+
+        if total_stake == 0 || approved_stake * 3 < total_stake * 2 {
+            return Err(NovaError::ProofInvalid(format!(
+                "light client block at height {} approved by {} of {} stake, below 2/3 threshold",
+                block.inner_lite.height, approved_stake, total_stake
+            )));
+        }
+
+        self.head_height = block.inner_lite.height;
+        // This block's own `next_bps` names the validator set that must
+        // approve whichever block comes next — exactly the set `bootstrap`
+        // seeds `current_validators` with, so every subsequent `advance`
+        // checks stake against the epoch the block just verified actually
+        // designates, not one epoch behind it.
+        self.current_validators = block.next_bps.clone().unwrap_or_default();
+        self.head = block.into();
+        Ok(true)
+    }
+
+    /// Verifies that `proof` Merkle-proves its outcome all the way up to a
+    /// block this client has independently validated via `advance` —
+    /// both legs of it, not just the first:
+    ///
+    /// 1. `outcome_proof.proof` folds the outcome hash up to its shard's
+    ///    outcome root, then `outcome_root_proof` folds that up to
+    ///    `block_header_lite.inner_lite.outcome_root` — proving the outcome
+    ///    belongs to the block `block_header_lite` claims to be.
+    /// 2. `block_proof` folds `block_header_lite.hash()` up to
+    ///    `self.head`'s `block_merkle_root` — proving that block is itself
+    ///    an ancestor of a header this client already validated as signed
+    ///    by >= 2/3 stake.
+    ///
+    /// Skipping either leg (as an earlier version of this function did)
+    /// leaves `block_header_lite` and its `outcome_root` exactly as
+    /// trusted as the untrusted RPC response that carried them — the
+    /// first leg alone only proves self-consistency between two fields
+    /// the gateway controls, not that either was ever part of the real
+    /// chain.
+    pub fn verify_execution_proof(
+        &self,
+        proof: &near_jsonrpc_primitives::types::light_client::RpcLightClientExecutionProofResponse,
+    ) -> Result<(), NovaError> {
+        if proof.block_header_lite.inner_lite.height > self.head_height {
+            return Err(NovaError::ProofInvalid(
+                "proof references a block newer than our verified head; advance the light client first".into(),
+            ));
+        }
+
+        let outcome_hash = near_primitives::hash::CryptoHash::hash_borsh(&proof.outcome_proof.to_hashes());
+        let shard_outcome_root = fold_merkle_path(outcome_hash, &proof.outcome_proof.proof);
+        let outcome_root = fold_merkle_path(shard_outcome_root, &proof.outcome_root_proof);
+        if outcome_root != proof.block_header_lite.inner_lite.outcome_root {
+            return Err(NovaError::ProofInvalid(
+                "execution outcome proof does not hash up to the block's outcome root".into(),
+            ));
+        }
+
+        let block_root = fold_merkle_path(proof.block_header_lite.hash(), &proof.block_proof);
+        if block_root != self.head.inner_lite.block_merkle_root {
+            return Err(NovaError::ProofInvalid(
+                "block inclusion proof does not hash up to our verified head's block merkle root".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn fold_merkle_path(leaf: CryptoHash, path: &[near_primitives::merkle::MerklePathItem]) -> CryptoHash {
+    path.iter().fold(leaf, |computed, step| step.combine(computed))
+}
+
+fn stake_of(v: &ValidatorStakeView) -> u128 {
+    match v {
+        ValidatorStakeView::V1(v1) => v1.stake,
+    }
+}
+
+/// Builds the RPC request for an `EXPERIMENTAL_light_client_proof` lookup of
+/// a transaction/receipt execution outcome on `account_id`.
+pub fn execution_proof_request(
+    id: near_jsonrpc_primitives::types::light_client::TransactionOrReceiptId,
+    light_client_head: CryptoHash,
+) -> RpcLightClientExecutionProofRequest {
+    RpcLightClientExecutionProofRequest {
+        id,
+        light_client_head,
+    }
+}
+
+/// Caches known contract accounts whose view calls should always be
+/// verified, used by `NovaSdk` to decide when to take the slow trustless path.
+pub fn default_verified_methods() -> HashMap<&'static str, AccountId> {
+    HashMap::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_crypto::{KeyType, PublicKey, Signature};
+    use near_primitives::views::{BlockHeaderInnerLiteView, ValidatorStakeViewV1};
+
+    fn validator(stake: u128) -> ValidatorStakeView {
+        ValidatorStakeView::V1(ValidatorStakeViewV1 {
+            account_id: "validator.test".parse().unwrap(),
+            public_key: PublicKey::empty(KeyType::ED25519),
+            stake,
+        })
+    }
+
+    // One signed-by-everyone approval per validator in `validators`, i.e.
+    // a block approved by the full current set's stake.
+    fn full_approvals(validators: &[ValidatorStakeView]) -> Vec<Option<Box<Signature>>> {
+        validators
+            .iter()
+            .map(|_| Some(Box::new(Signature::empty(KeyType::ED25519))))
+            .collect()
+    }
+
+    fn block_at(height: BlockHeight, next_bps: Vec<ValidatorStakeView>, approvals: Vec<Option<Box<Signature>>>) -> LightClientBlockView {
+        LightClientBlockView {
+            inner_lite: BlockHeaderInnerLiteView {
+                height,
+                ..Default::default()
+            },
+            next_bps: Some(next_bps),
+            approvals_after_next: approvals,
+            ..Default::default()
+        }
+    }
+
+    fn client_with(head_height: BlockHeight, current_validators: Vec<ValidatorStakeView>) -> LightClient {
+        LightClient {
+            head: LightClientBlockLiteView {
+                inner_lite: BlockHeaderInnerLiteView {
+                    height: head_height,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            head_height,
+            current_validators,
+        }
+    }
+
+    #[test]
+    fn verify_and_adopt_rejects_below_two_thirds_stake() {
+        let s1 = vec![validator(100), validator(100), validator(100)];
+        let mut client = client_with(1, s1.clone());
+
+        // Only one of three equal-stake validators approved: 1/3, not 2/3.
+        let mut approvals = vec![None, None];
+        approvals.insert(0, Some(Box::new(Signature::empty(KeyType::ED25519))));
+        let block = block_at(2, vec![validator(100)], approvals);
+
+        assert!(client.verify_and_adopt(block).is_err());
+    }
+
+    #[test]
+    fn verify_and_adopt_accepts_two_thirds_stake_and_rotates_validators() {
+        let s1 = vec![validator(100), validator(100), validator(100)];
+        let mut client = client_with(1, s1.clone());
+
+        let s2 = vec![validator(200)];
+        let block2 = block_at(2, s2.clone(), full_approvals(&s1));
+        assert!(client.verify_and_adopt(block2).unwrap());
+        assert_eq!(client.head_height, 2);
+
+        // Regression guard for the stale-epoch bug: after the block that
+        // designates `s2` as next is adopted, `current_validators` must be
+        // `s2` itself — not the set that approved it (`s1`) and not
+        // whatever a one-epoch-delayed indirection would have left behind.
+        let s3 = vec![validator(300)];
+        let block3 = block_at(3, s3, full_approvals(&s2));
+        assert!(client.verify_and_adopt(block3).unwrap());
+        assert_eq!(client.head_height, 3);
+    }
+
+    #[test]
+    fn verify_and_adopt_rejects_third_advance_against_stale_validators() {
+        // Same three-call trace the review walked through: if `current_validators`
+        // were still lagging by one epoch at call 3, a block correctly signed by
+        // `s3` (not `s2`) would be wrongly rejected for "insufficient stake".
+        let s1 = vec![validator(100)];
+        let mut client = client_with(1, s1.clone());
+
+        let s2 = vec![validator(200)];
+        client.verify_and_adopt(block_at(2, s2.clone(), full_approvals(&s1))).unwrap();
+
+        let s3 = vec![validator(300)];
+        client.verify_and_adopt(block_at(3, s3.clone(), full_approvals(&s2))).unwrap();
+
+        let s4 = vec![validator(400)];
+        let block4 = block_at(4, s4, full_approvals(&s3));
+        assert!(
+            client.verify_and_adopt(block4).is_ok(),
+            "a block approved by the current epoch's own validators must verify"
+        );
+    }
+
+    #[test]
+    fn fold_merkle_path_empty_path_returns_leaf_unchanged() {
+        let leaf = CryptoHash::hash_bytes(b"leaf");
+        assert_eq!(fold_merkle_path(leaf, &[]), leaf);
+    }
+}
@@ -0,0 +1,108 @@
+//! Offline / air-gapped signing support.
+//!
+//! Splits "build a transaction" from "sign it" from "broadcast it" so the
+//! ed25519 (or secp256k1) key never has to live on a machine that talks to
+//! the network: an online, keyless `NovaSdk` instance builds an
+//! [`UnsignedTx`] (fetching the nonce and recent block hash over RPC), an
+//! air-gapped instance signs it with [`sign_offline`] purely in memory, and
+//! the resulting base64 blob is carried back (by hand, QR code, USB stick,
+//! whatever) to an online instance for [`NovaSdk::broadcast_signed`].
+
+use near_crypto::PublicKey;
+use near_primitives::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_primitives::hash::CryptoHash;
+use near_primitives::transaction::{Action, SignedTransaction, Transaction, TransactionV0};
+use near_primitives::types::{AccountId, BlockHeight, Nonce};
+
+use crate::{NovaError, Signer};
+
+/// A fully-specified, not-yet-signed transaction. Borsh-serializable so it
+/// can cross the air gap in the other direction too (e.g. presented to the
+/// offline signer as a QR code) if the caller wants to review it first.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct UnsignedTx {
+    pub signer_id: AccountId,
+    pub public_key: PublicKey,
+    pub nonce: Nonce,
+    pub receiver_id: AccountId,
+    pub block_hash: CryptoHash,
+    pub actions: Vec<Action>,
+}
+
+impl UnsignedTx {
+    fn to_transaction(&self) -> Transaction {
+        Transaction::V0(TransactionV0 {
+            signer_id: self.signer_id.clone(),
+            public_key: self.public_key.clone(),
+            nonce: self.nonce,
+            receiver_id: self.receiver_id.clone(),
+            block_hash: self.block_hash,
+            actions: self.actions.clone(),
+        })
+    }
+}
+
+/// A signed transaction, borsh-serialized and base64-encoded so it can be
+/// copied between machines as plain text.
+#[derive(Debug, Clone)]
+pub struct SignedTxEnvelope(pub String);
+
+impl SignedTxEnvelope {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn decode(b64: &str) -> Result<SignedTransaction, NovaError> {
+        let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, b64)
+            .map_err(|_| NovaError::InvalidKey)?;
+        SignedTransaction::try_from_slice(&bytes)
+            .map_err(|e| NovaError::Near(format!("malformed signed transaction: {}", e)))
+    }
+}
+
+/// Builds an unsigned transaction. Pass `nonce` and `block_hash` explicitly
+/// when the caller is fully air-gapped (no RPC access); they're normally
+/// obtained ahead of time from an online host via
+/// `NovaSdk::fetch_nonce_and_block_hash`.
+pub fn build_unsigned(
+    signer_id: AccountId,
+    public_key: PublicKey,
+    receiver_id: AccountId,
+    actions: Vec<Action>,
+    nonce: Nonce,
+    block_hash: CryptoHash,
+) -> UnsignedTx {
+    UnsignedTx {
+        signer_id,
+        public_key,
+        nonce,
+        receiver_id,
+        block_hash,
+        actions,
+    }
+}
+
+/// Signs an unsigned transaction entirely in memory using any `Signer`
+/// implementation. Never touches the network, so this is safe to run on an
+/// air-gapped host even when `signer` is backed by a secp256k1 key.
+pub fn sign_with(unsigned: &UnsignedTx, signer: &dyn Signer) -> Result<SignedTxEnvelope, NovaError> {
+    let transaction = unsigned.to_transaction();
+    let hash = transaction.get_hash_and_size().0;
+    let signature = signer.sign(hash.as_ref());
+    let signed = SignedTransaction::new(signature, transaction);
+    let bytes = borsh::to_vec(&signed)
+        .map_err(|e| NovaError::Near(format!("failed to serialize signed transaction: {}", e)))?;
+    Ok(SignedTxEnvelope(base64::Engine::encode(
+        &base64::engine::general_purpose::STANDARD,
+        bytes,
+    )))
+}
+
+/// What an online host needs to hand to an offline signer: the access
+/// key's next nonce and a recent block hash to anchor the transaction to.
+#[derive(Debug, Clone, Copy)]
+pub struct NonceAndBlockHash {
+    pub nonce: Nonce,
+    pub block_hash: CryptoHash,
+    pub block_height: BlockHeight,
+}
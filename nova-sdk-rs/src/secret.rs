@@ -0,0 +1,47 @@
+//! Key material that zeroizes itself on drop and never renders verbatim in
+//! `Debug` output, so a private key or a decrypted group key doesn't
+//! linger in memory (or a stray log line) longer than it has to.
+
+use base64::Engine;
+use zeroize::Zeroizing;
+
+use crate::NovaError;
+
+/// Owns sensitive bytes behind a `Zeroizing` buffer. Construct via [`Secret::new`]
+/// for raw bytes already in hand, or [`Secret::from_base64`] to validate
+/// base64-encoded key material (length included) before it's used for
+/// anything — so a malformed or short key is rejected locally instead of
+/// round-tripping to the contract and failing there.
+pub struct Secret(Zeroizing<Vec<u8>>);
+
+impl Secret {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Secret(Zeroizing::new(bytes))
+    }
+
+    /// Decodes `b64` and rejects it unless it decodes to exactly
+    /// `expected_len` bytes (AES-256 keys are 32 bytes).
+    pub fn from_base64(b64: &str, expected_len: usize) -> Result<Self, NovaError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(b64)
+            .map_err(|_| NovaError::InvalidKey)?;
+        if bytes.len() != expected_len {
+            return Err(NovaError::InvalidKey);
+        }
+        Ok(Secret(Zeroizing::new(bytes)))
+    }
+
+    pub fn expose_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.0.as_slice())
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
@@ -0,0 +1,185 @@
+//! Shamir secret sharing over GF(2^8), for splitting a 32-byte group key
+//! across members instead of handing each one the whole thing.
+//!
+//! `distribute_group_key` wraps the same secret for every member, so any
+//! `t` of them (really, any one of them) already holds enough to decrypt
+//! everything the group ever will. This splits the secret byte-by-byte
+//! into a degree-`(threshold - 1)` polynomial per byte, evaluates each at
+//! a distinct nonzero x-index per member, and lets any `threshold` of
+//! those shares reconstruct the original secret via Lagrange
+//! interpolation at x=0 — no `threshold - 1` subset of members can. Field
+//! arithmetic uses the AES reduction polynomial `0x11b`, the same GF(2^8)
+//! most secret-sharing implementations (and AES's own S-box) are built on.
+
+use crate::NovaError;
+
+const SECRET_LEN: usize = 32;
+
+/// Multiplies two GF(2^8) elements under the AES reduction polynomial.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0u8;
+    for _ in 0..8 {
+        if b & 1 != 0 {
+            result ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    result
+}
+
+/// Inverts a nonzero GF(2^8) element by exponentiation (`a^254 == a^-1`,
+/// since every nonzero element has multiplicative order dividing 255).
+fn gf_inv(a: u8) -> u8 {
+    let a2 = gf_mul(a, a);
+    let a4 = gf_mul(a2, a2);
+    let a8 = gf_mul(a4, a4);
+    let a16 = gf_mul(a8, a8);
+    let a32 = gf_mul(a16, a16);
+    let a64 = gf_mul(a32, a32);
+    let a128 = gf_mul(a64, a64);
+    // 254 = 128 + 64 + 32 + 16 + 8 + 4 + 2
+    gf_mul(gf_mul(gf_mul(a128, a64), gf_mul(a32, a16)), gf_mul(gf_mul(a8, a4), a2))
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    gf_mul(a, gf_inv(b))
+}
+
+/// Evaluates the per-byte degree-`(threshold - 1)` polynomials `secret[i]
+/// + a_1 x + ... + a_{threshold-1} x^{threshold-1}` at each of
+/// `x_indices`, returning one 32-byte share per index. Coefficients are
+/// sampled fresh per call, so splitting the same secret twice yields
+/// unrelated shares.
+pub fn split_secret(secret: &[u8; SECRET_LEN], threshold: u8, x_indices: &[u8]) -> Result<Vec<[u8; SECRET_LEN]>, NovaError> {
+    if threshold == 0 || (threshold as usize) > x_indices.len() {
+        return Err(NovaError::Validation(format!(
+            "threshold {} must be between 1 and the member count {}",
+            threshold,
+            x_indices.len()
+        )));
+    }
+    if x_indices.iter().any(|&x| x == 0) {
+        return Err(NovaError::Validation("Shamir x-index 0 is reserved for the secret itself".to_string()));
+    }
+    let mut seen = std::collections::HashSet::new();
+    if !x_indices.iter().all(|&x| seen.insert(x)) {
+        return Err(NovaError::Validation("duplicate Shamir x-index assigned to two members".to_string()));
+    }
+
+    let mut shares = vec![[0u8; SECRET_LEN]; x_indices.len()];
+    for byte_pos in 0..SECRET_LEN {
+        let mut coefficients = vec![secret[byte_pos]];
+        let mut extra = vec![0u8; (threshold - 1) as usize];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut extra);
+        coefficients.extend(extra);
+
+        for (share, &x) in shares.iter_mut().zip(x_indices) {
+            share[byte_pos] = eval_polynomial(&coefficients, x);
+        }
+    }
+    Ok(shares)
+}
+
+fn eval_polynomial(coefficients: &[u8], x: u8) -> u8 {
+    // Horner's method: highest-degree coefficient first.
+    coefficients.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+/// Reconstructs the 32-byte secret from `shares` (x-index, share bytes)
+/// via Lagrange interpolation at x=0, independently per byte position.
+/// Any `threshold`-sized subset of valid shares reconstructs the same
+/// secret; a wrong or insufficient subset silently produces garbage
+/// (verify against the on-chain commitment, not the absence of an error).
+pub fn reconstruct_secret(shares: &[(u8, [u8; SECRET_LEN])]) -> Result<[u8; SECRET_LEN], NovaError> {
+    if shares.is_empty() {
+        return Err(NovaError::Validation("need at least one share to reconstruct a secret".to_string()));
+    }
+    let mut seen = std::collections::HashSet::new();
+    if shares.iter().any(|&(x, _)| x == 0) || !shares.iter().all(|&(x, _)| seen.insert(x)) {
+        return Err(NovaError::Validation("Shamir shares must have distinct, nonzero x-indices".to_string()));
+    }
+
+    let mut secret = [0u8; SECRET_LEN];
+    for byte_pos in 0..SECRET_LEN {
+        let mut acc = 0u8;
+        for &(xi, ref share_i) in shares {
+            // Lagrange basis polynomial l_i(0) = product over j != i of
+            // (0 - x_j) / (x_i - x_j), and subtraction is XOR in GF(2^8).
+            let mut numerator = 1u8;
+            let mut denominator = 1u8;
+            for &(xj, _) in shares {
+                if xj == xi {
+                    continue;
+                }
+                numerator = gf_mul(numerator, xj);
+                denominator = gf_mul(denominator, xi ^ xj);
+            }
+            let basis = gf_div(numerator, denominator);
+            acc ^= gf_mul(share_i[byte_pos], basis);
+        }
+        secret[byte_pos] = acc;
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_and_reconstruct_with_exact_threshold_round_trips() {
+        let secret = *b"0123456789abcdef0123456789abcdef";
+        let x_indices = [1, 2, 3, 4, 5];
+        let shares = split_secret(&secret, 3, &x_indices).unwrap();
+
+        let subset: Vec<(u8, [u8; SECRET_LEN])> =
+            x_indices.iter().zip(shares.iter()).take(3).map(|(&x, &s)| (x, s)).collect();
+        let reconstructed = reconstruct_secret(&subset).unwrap();
+        assert_eq!(reconstructed, secret);
+    }
+
+    #[test]
+    fn reconstruct_with_any_threshold_subset_agrees() {
+        let secret = *b"threshold-subsets-should-all-agr";
+        let x_indices = [1, 2, 3, 4, 5];
+        let shares = split_secret(&secret, 3, &x_indices).unwrap();
+        let all: Vec<(u8, [u8; SECRET_LEN])> = x_indices.iter().zip(shares.iter()).map(|(&x, &s)| (x, s)).collect();
+
+        let first_three: Vec<_> = all[..3].to_vec();
+        let last_three: Vec<_> = all[2..5].to_vec();
+        assert_eq!(reconstruct_secret(&first_three).unwrap(), secret);
+        assert_eq!(reconstruct_secret(&last_three).unwrap(), secret);
+    }
+
+    #[test]
+    fn fewer_than_threshold_shares_does_not_reliably_reconstruct() {
+        let secret = *b"below-threshold-should-not-recov";
+        let x_indices = [1, 2, 3, 4, 5];
+        let shares = split_secret(&secret, 3, &x_indices).unwrap();
+        let too_few: Vec<(u8, [u8; SECRET_LEN])> = x_indices.iter().zip(shares.iter()).take(2).map(|(&x, &s)| (x, s)).collect();
+        assert_ne!(reconstruct_secret(&too_few).unwrap(), secret);
+    }
+
+    #[test]
+    fn rejects_zero_x_index() {
+        let secret = [0u8; SECRET_LEN];
+        assert!(split_secret(&secret, 2, &[0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_x_index() {
+        let secret = [0u8; SECRET_LEN];
+        assert!(split_secret(&secret, 2, &[1, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn rejects_threshold_above_member_count() {
+        let secret = [0u8; SECRET_LEN];
+        assert!(split_secret(&secret, 4, &[1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! Pluggable transaction signing.
+//!
+//! `NovaSdk` used to hardcode an in-process ed25519 key. `Signer` abstracts
+//! "can produce a NEAR signature for this account" so the SDK never has to
+//! see private key bytes when the caller wants to delegate to a remote
+//! wallet or hardware signer instead.
+
+use near_crypto::{InMemorySigner, PublicKey, SecretKey, Signature};
+use near_primitives::types::AccountId;
+
+use crate::NovaError;
+
+/// Anything that can sign NEAR transaction bytes on behalf of an account.
+/// Implement this to plug in a Ledger, a remote signing service, or any
+/// other backend that keeps key material out of this process.
+pub trait Signer: Send + Sync {
+    fn public_key(&self) -> PublicKey;
+    fn sign(&self, message: &[u8]) -> Signature;
+    fn account_id(&self) -> AccountId;
+
+    /// Derives an X25519 Diffie-Hellman shared secret against an ephemeral
+    /// public key, for unwrapping a per-member group data key (see
+    /// `crate::group_key`). Only meaningful for ed25519 signers — a NEAR
+    /// ed25519 key converts to Curve25519 via the standard birational map
+    /// libsodium's `crypto_sign_ed25519_sk_to_curve25519` uses; secp256k1
+    /// keys have no such mapping and return `NovaError::Signing`.
+    fn ecdh_shared_secret(&self, their_x25519_public: &[u8; 32]) -> Result<[u8; 32], NovaError>;
+}
+
+/// In-process ed25519 signer: the same key material `with_signer` has
+/// always accepted, now behind the `Signer` trait.
+pub struct Ed25519Signer(InMemorySigner);
+
+impl Ed25519Signer {
+    pub fn new(account_id: AccountId, secret_key: SecretKey) -> Result<Self, NovaError> {
+        match secret_key {
+            SecretKey::ED25519(_) => Ok(Ed25519Signer(InMemorySigner::from_secret_key(account_id, secret_key))),
+            _ => Err(NovaError::Signing("expected an ed25519 secret key".to_string())),
+        }
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn public_key(&self) -> PublicKey {
+        self.0.public_key.clone()
+    }
+
+    fn sign(&self, message: &[u8]) -> Signature {
+        near_crypto::Signer::InMemory(self.0.clone()).sign(message)
+    }
+
+    fn account_id(&self) -> AccountId {
+        self.0.account_id.clone()
+    }
+
+    fn ecdh_shared_secret(&self, their_x25519_public: &[u8; 32]) -> Result<[u8; 32], NovaError> {
+        let SecretKey::ED25519(secret) = &self.0.secret_key else {
+            return Err(NovaError::Signing("expected an ed25519 secret key".to_string()));
+        };
+        // NEAR's ed25519 secret key bytes are the 64-byte dalek keypair
+        // (32-byte seed || 32-byte public key); hash the seed with
+        // SHA-512 and take the first half as the matching X25519 static
+        // secret, the same construction `crypto_sign_ed25519_sk_to_curve25519`
+        // uses.
+        use sha2::{Digest, Sha512};
+        let hash = Sha512::digest(&secret.0[..32]);
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes.copy_from_slice(&hash[..32]);
+        let my_secret = x25519_dalek::StaticSecret::from(scalar_bytes);
+        let their_public = x25519_dalek::PublicKey::from(*their_x25519_public);
+        Ok(*my_secret.diffie_hellman(&their_public).as_bytes())
+    }
+}
+
+/// In-process secp256k1 signer, for dApps migrating from EVM tooling that
+/// already hold a secp256k1 key. NEAR access keys accept either curve.
+pub struct Secp256k1Signer(InMemorySigner);
+
+impl Secp256k1Signer {
+    pub fn new(account_id: AccountId, secret_key: SecretKey) -> Result<Self, NovaError> {
+        match secret_key {
+            SecretKey::SECP256K1(_) => Ok(Secp256k1Signer(InMemorySigner::from_secret_key(account_id, secret_key))),
+            _ => Err(NovaError::Signing("expected a secp256k1 secret key".to_string())),
+        }
+    }
+}
+
+impl Signer for Secp256k1Signer {
+    fn public_key(&self) -> PublicKey {
+        self.0.public_key.clone()
+    }
+
+    fn sign(&self, message: &[u8]) -> Signature {
+        near_crypto::Signer::InMemory(self.0.clone()).sign(message)
+    }
+
+    fn account_id(&self) -> AccountId {
+        self.0.account_id.clone()
+    }
+
+    fn ecdh_shared_secret(&self, _their_x25519_public: &[u8; 32]) -> Result<[u8; 32], NovaError> {
+        Err(NovaError::Signing("secp256k1 signers do not support X25519 group-key wrapping".to_string()))
+    }
+}
@@ -0,0 +1,195 @@
+//! Streaming chunked AES-CBC encryption with in-flight SHA-256 hashing, so
+//! `composite_upload_stream` never has to hold a whole file in memory.
+
+use aes::Aes256;
+use cbc::cipher::{block_padding::Pkcs7, BlockEncryptMut, BlockDecryptMut, KeyIvInit};
+use sha2::{Digest, Sha256};
+
+use crate::NovaError;
+
+/// Read/processed in 1 MiB chunks; large enough to amortize per-chunk
+/// overhead, small enough to keep memory use flat regardless of file size.
+pub const CHUNK_SIZE: usize = 1024 * 1024;
+
+const BLOCK_SIZE: usize = 16;
+
+type Aes256CbcEnc = cbc::Encryptor<Aes256>;
+type Aes256CbcDec = cbc::Decryptor<Aes256>;
+
+/// Encrypts a byte stream incrementally under AES-256-CBC while hashing the
+/// *plaintext* as it flows through, so the caller gets the file hash for
+/// free without a second pass over the data.
+pub struct ChunkEncryptor {
+    cipher: Aes256CbcEnc,
+    hasher: Sha256,
+    carry: Vec<u8>,
+}
+
+impl ChunkEncryptor {
+    pub fn new(key: &[u8; 32], iv: &[u8; 16]) -> Self {
+        ChunkEncryptor {
+            cipher: Aes256CbcEnc::new(key.into(), iv.into()),
+            hasher: Sha256::new(),
+            carry: Vec::with_capacity(BLOCK_SIZE),
+        }
+    }
+
+    /// Feeds a plaintext chunk in. Returns ciphertext for every whole block
+    /// this chunk completed; a partial trailing block is carried over to the
+    /// next call (or to `finalize` for the last one).
+    pub fn update(&mut self, chunk: &[u8]) -> Vec<u8> {
+        self.hasher.update(chunk);
+
+        self.carry.extend_from_slice(chunk);
+        let whole_len = (self.carry.len() / BLOCK_SIZE) * BLOCK_SIZE;
+        if whole_len == 0 {
+            return Vec::new();
+        }
+
+        let mut to_encrypt = self.carry.split_off(0);
+        let remainder = to_encrypt.split_off(whole_len);
+        self.carry = remainder;
+
+        for block in to_encrypt.chunks_mut(BLOCK_SIZE) {
+            self.cipher.encrypt_block_mut(block.into());
+        }
+        to_encrypt
+    }
+
+    /// Pads the final partial block with PKCS7 and returns the last
+    /// ciphertext bytes together with the SHA-256 of everything fed in.
+    pub fn finalize(mut self) -> Result<(Vec<u8>, [u8; 32]), NovaError> {
+        let mut buffer = self.carry.clone();
+        buffer.resize(BLOCK_SIZE, 0);
+        let final_block = self
+            .cipher
+            .encrypt_padded_mut::<Pkcs7>(&mut buffer, self.carry.len())
+            .map_err(|_| NovaError::Near("streaming encryption padding failed".to_string()))?
+            .to_vec();
+        Ok((final_block, self.hasher.finalize().into()))
+    }
+}
+
+/// Mirror of `ChunkEncryptor` for decrypt: feed ciphertext chunks in,
+/// call `finalize` once the stream is exhausted to strip PKCS7 padding and
+/// recover the file hash of the recovered plaintext.
+pub struct ChunkDecryptor {
+    cipher: Aes256CbcDec,
+    hasher: Sha256,
+    carry: Vec<u8>,
+}
+
+impl ChunkDecryptor {
+    pub fn new(key: &[u8; 32], iv: &[u8; 16]) -> Self {
+        ChunkDecryptor {
+            cipher: Aes256CbcDec::new(key.into(), iv.into()),
+            hasher: Sha256::new(),
+            carry: Vec::with_capacity(BLOCK_SIZE),
+        }
+    }
+
+    /// Decrypts whole ciphertext blocks and keeps the rest (at least one
+    /// block, since the last block always carries the PKCS7 padding and
+    /// can't be decrypted until `finalize` sees it's truly the last one).
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, NovaError> {
+        self.carry.extend_from_slice(chunk);
+        if self.carry.len() <= BLOCK_SIZE {
+            return Ok(Vec::new());
+        }
+
+        let keep_last_block = self.carry.len() - BLOCK_SIZE;
+        let whole_len = (keep_last_block / BLOCK_SIZE) * BLOCK_SIZE;
+        if whole_len == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut to_decrypt = self.carry.split_off(0);
+        let remainder = to_decrypt.split_off(whole_len);
+        self.carry = remainder;
+
+        for block in to_decrypt.chunks_mut(BLOCK_SIZE) {
+            self.cipher.decrypt_block_mut(block.into());
+        }
+        self.hasher.update(&to_decrypt);
+        Ok(to_decrypt)
+    }
+
+    pub fn finalize(mut self) -> Result<(Vec<u8>, [u8; 32]), NovaError> {
+        let mut buffer = self.carry;
+        let plaintext = self
+            .cipher
+            .decrypt_padded_mut::<Pkcs7>(&mut buffer)
+            .map_err(|_| NovaError::Near("streaming decryption padding failed".to_string()))?
+            .to_vec();
+        self.hasher.update(&plaintext);
+        Ok((plaintext, self.hasher.finalize().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [9u8; 32];
+    const IV: [u8; 16] = [3u8; 16];
+
+    fn encrypt_in_chunks(plaintext: &[u8], chunk_size: usize) -> (Vec<u8>, [u8; 32]) {
+        let mut encryptor = ChunkEncryptor::new(&KEY, &IV);
+        let mut ciphertext = Vec::new();
+        for chunk in plaintext.chunks(chunk_size.max(1)) {
+            ciphertext.extend(encryptor.update(chunk));
+        }
+        let (tail, hash) = encryptor.finalize().unwrap();
+        ciphertext.extend(tail);
+        (ciphertext, hash)
+    }
+
+    fn decrypt_in_chunks(ciphertext: &[u8], chunk_size: usize) -> (Vec<u8>, [u8; 32]) {
+        let mut decryptor = ChunkDecryptor::new(&KEY, &IV);
+        let mut plaintext = Vec::new();
+        for chunk in ciphertext.chunks(chunk_size.max(1)) {
+            plaintext.extend(decryptor.update(chunk).unwrap());
+        }
+        let (tail, hash) = decryptor.finalize().unwrap();
+        plaintext.extend(tail);
+        (plaintext, hash)
+    }
+
+    // Varies both the plaintext length relative to the 16-byte AES block
+    // size and the chunk size data arrives in, to pin down the carry-buffer
+    // bookkeeping on both `update` paths at every boundary condition:
+    // shorter than a block, exactly a block, and split mid-block across
+    // calls in either direction.
+    #[test]
+    fn round_trips_across_block_and_chunk_size_boundaries() {
+        let plaintext_lens = [0usize, 1, 15, 16, 17, 31, 32, 33, 100, CHUNK_SIZE + 7];
+        let chunk_sizes = [1usize, 5, 16, 17, 32, 1024, CHUNK_SIZE];
+
+        for &len in &plaintext_lens {
+            let plaintext: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            for &chunk_size in &chunk_sizes {
+                let (ciphertext, enc_hash) = encrypt_in_chunks(&plaintext, chunk_size);
+                for &decrypt_chunk_size in &chunk_sizes {
+                    let (decrypted, dec_hash) = decrypt_in_chunks(&ciphertext, decrypt_chunk_size);
+                    assert_eq!(decrypted, plaintext, "len={len} enc_chunk={chunk_size} dec_chunk={decrypt_chunk_size}");
+                    assert_eq!(dec_hash, enc_hash);
+                    assert_eq!(dec_hash, crate::sha256_hash(&plaintext));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decryptor_surfaces_tampered_plaintext_via_hash_mismatch() {
+        // Flip a byte in the first ciphertext block (not the last, whose
+        // PKCS7 padding `decrypt_padded_mut` would just reject outright) so
+        // decryption still succeeds but recovers the wrong plaintext — the
+        // case `composite_retrieve_stream_unverified`'s CID check exists to catch.
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+        let (mut ciphertext, _) = encrypt_in_chunks(plaintext, CHUNK_SIZE);
+        ciphertext[0] ^= 0xff;
+        let (decrypted, hash) = decrypt_in_chunks(&ciphertext, CHUNK_SIZE);
+        assert_ne!(decrypted, plaintext);
+        assert_ne!(hash, crate::sha256_hash(plaintext));
+    }
+}
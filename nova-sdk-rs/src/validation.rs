@@ -0,0 +1,40 @@
+//! Client-side pre-flight checks (Namada's "validate before submitting"
+//! approach): cheap, local or view-only checks run before a signed
+//! transaction is built, so an obviously-doomed call fails with a
+//! [`NovaError::Validation`] instead of burning gas on a contract panic.
+
+use crate::NovaError;
+
+/// Accepts CIDv0 (base58btc, `Qm` + 44 characters) and CIDv1 (base32,
+/// `bafy...`) content identifiers, the two formats Pinata returns.
+pub fn validate_cid(cid: &str) -> Result<(), NovaError> {
+    if let Some(rest) = cid.strip_prefix("Qm") {
+        if cid.len() == 46 && rest.bytes().all(is_base58_byte) {
+            return Ok(());
+        }
+        return Err(NovaError::Validation(format!("malformed CIDv0: {}", cid)));
+    }
+    if let Some(rest) = cid.strip_prefix("bafy") {
+        if !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()) {
+            return Ok(());
+        }
+        return Err(NovaError::Validation(format!("malformed CIDv1: {}", cid)));
+    }
+    Err(NovaError::Validation(format!("unrecognized CID format: {}", cid)))
+}
+
+fn is_base58_byte(b: u8) -> bool {
+    matches!(b, b'1'..=b'9' | b'A'..=b'H' | b'J'..=b'N' | b'P'..=b'Z' | b'a'..=b'k' | b'm'..=b'z')
+}
+
+/// Validates that `s` is lowercase hex encoding exactly `expected_len` bytes
+/// (the file hash is always a SHA-256 digest, i.e. 32 bytes).
+pub fn validate_hex(s: &str, expected_len: usize) -> Result<(), NovaError> {
+    if s.len() == expected_len * 2 && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Ok(());
+    }
+    Err(NovaError::Validation(format!(
+        "expected {}-byte hex string, got {:?}",
+        expected_len, s
+    )))
+}
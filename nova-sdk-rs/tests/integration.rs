@@ -1,4 +1,4 @@
-use nova_sdk_rs::{NovaSdk, NovaError};
+use nova_sdk_rs::{NovaSdk, NovaError, Secret};
 use rand::RngCore; // Import RngCore trait for fill_bytes
 use base64::{Engine as _, engine::general_purpose}; // New base64 API
 
@@ -198,11 +198,9 @@ async fn test_get_group_key_authorized_integration() {
     );
     
     let key = sdk.get_group_key("test_group", &account_id).await.unwrap();
-    assert!(!key.is_empty(), "Authorized key should be non-empty base64");
-    assert!(key.len() > 20, "Base64 key should be reasonable length (e.g., 44 chars for 32 bytes)");
-    
+    assert_eq!(key.expose_bytes().len(), 32, "Authorized key should decode to a 32-byte AES-256 key");
+
     println!("✅ Retrieved group key for authorized account: {}", account_id);
-    println!("   Key length: {} chars", key.len());
 }
 
 #[tokio::test]
@@ -319,7 +317,7 @@ async fn test_revoke_group_member_integration() {
     
     // Assume a known member exists; revoke and verify post-revoke with is_authorized
     let member_to_revoke = "known.member.testnet"; // Replace with actual test member if needed
-    let result_revoke = sdk.revoke_group_member("test_group", member_to_revoke).await;
+    let result_revoke = sdk.revoke_group_member("test_group", member_to_revoke, false).await;
     match result_revoke {
         Ok(_) => {
             println!("✅ Revoked member: {}", member_to_revoke);
@@ -361,14 +359,15 @@ async fn test_store_group_key_integration() {
     let mut key_bytes = [0u8; 32];
     rng.fill_bytes(&mut key_bytes);
     let key_b64 = general_purpose::STANDARD.encode(key_bytes);
-    
-    let result = sdk.store_group_key("test_group", &key_b64).await;
+    let key = Secret::from_base64(&key_b64, 32).unwrap();
+
+    let result = sdk.store_group_key("test_group", &key, false).await;
     match result {
         Ok(_) => {
             println!("✅ Stored group key for test_group");
             // Verify: Fetch and check length
             let fetched_key = sdk.get_group_key("test_group", &account_id_str).await.unwrap();
-            assert_eq!(fetched_key, key_b64, "Stored and fetched key should match");
+            assert_eq!(fetched_key.to_base64(), key_b64, "Stored and fetched key should match");
         }
         Err(e) => panic!("Unexpected store error: {}", e),
     }
@@ -397,9 +396,9 @@ async fn test_record_transaction_integration() {
     
     // Dummy data for tx
     let dummy_file_hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"; // SHA256 of empty
-    let dummy_ipfs_hash = "QmDummyCIDForTest";
-    
-    let result = sdk.record_transaction("test_group", &account_id_str, dummy_file_hash, dummy_ipfs_hash).await;
+    let dummy_ipfs_hash = "QmYwAPJzv5CZsnAzt8auVZRnSW6aUezbbEMzyuUaVEF93A";
+
+    let result = sdk.record_transaction("test_group", &account_id_str, dummy_file_hash, dummy_ipfs_hash, None, false).await;
     match result {
         Ok(trans_id) => {
             println!("✅ Recorded transaction: {}", trans_id);